@@ -0,0 +1,198 @@
+use std::io::ErrorKind;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::symbol::SYMBOL;
+
+#[cfg(test)]
+mod tests;
+
+/// Byte range as it is described in the http `Range` header. Bytes are counted from 0, `end` is inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `start-end`, for example `0-99`
+    Inclusive { start: u64, end: u64 },
+    /// `start-`, from the given byte up to the end of the file
+    From { start: u64 },
+    /// `-length`, last `length` bytes of the file
+    Suffix { length: u64 },
+}
+
+/// Content of the resolved range. `start` and `end` are inclusive offsets in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteRangeContent {
+    pub start: u64,
+    pub end: u64,
+    pub content: Vec<u8>,
+}
+
+impl ByteRange {
+    pub const UNIT: &'static str = "bytes";
+    /// Maximum total length of the ranges read into memory at once, 16 MiB.
+    /// Larger portions of the file should be streamed with `FileExt::open_reader`
+    pub const MAX_TOTAL_LENGTH: u64 = 16 * 1024 * 1024;
+
+    /// Parses value of the `Range` header, for example `bytes=0-99,200-299,-500`
+    pub fn parse_header(header_value: &str) -> Result<Vec<ByteRange>, FileExtError> {
+        let boxed_split = header_value.trim().split_once(SYMBOL.equals);
+        if boxed_split.is_none() {
+            let message = format!("range does not specify unit: {}", header_value);
+            return Err(ByteRange::invalid_range(message))
+        }
+
+        let (unit, ranges) = boxed_split.unwrap();
+        if unit.trim() != ByteRange::UNIT {
+            let message = format!("unsupported range unit: {}", unit);
+            return Err(ByteRange::invalid_range(message))
+        }
+
+        let mut range_list = vec![];
+        for range in ranges.split(SYMBOL.comma) {
+            let range = range.trim();
+            if range.is_empty() {
+                continue;
+            }
+
+            let boxed_range = ByteRange::parse(range);
+            if boxed_range.is_err() {
+                let error = boxed_range.err().unwrap();
+                return Err(error)
+            }
+            range_list.push(boxed_range.unwrap());
+        }
+
+        if range_list.is_empty() {
+            let message = format!("no ranges specified: {}", header_value);
+            return Err(ByteRange::invalid_range(message))
+        }
+
+        Ok(range_list)
+    }
+
+    /// Parses single range, for example `0-99`, `200-` or `-500`
+    pub fn parse(range: &str) -> Result<ByteRange, FileExtError> {
+        let boxed_split = range.trim().split_once(SYMBOL.hyphen);
+        if boxed_split.is_none() {
+            let message = format!("range is missing hyphen: {}", range);
+            return Err(ByteRange::invalid_range(message))
+        }
+
+        let (start, end) = boxed_split.unwrap();
+        let start = start.trim();
+        let end = end.trim();
+
+        if start.is_empty() {
+            let boxed_length = end.parse::<u64>();
+            if boxed_length.is_err() {
+                let message = format!("unable to parse suffix length: {}", range);
+                return Err(ByteRange::invalid_range(message))
+            }
+            return Ok(ByteRange::Suffix { length: boxed_length.unwrap() })
+        }
+
+        let boxed_start = start.parse::<u64>();
+        if boxed_start.is_err() {
+            let message = format!("unable to parse range start: {}", range);
+            return Err(ByteRange::invalid_range(message))
+        }
+        let start = boxed_start.unwrap();
+
+        if end.is_empty() {
+            return Ok(ByteRange::From { start })
+        }
+
+        let boxed_end = end.parse::<u64>();
+        if boxed_end.is_err() {
+            let message = format!("unable to parse range end: {}", range);
+            return Err(ByteRange::invalid_range(message))
+        }
+        let end = boxed_end.unwrap();
+
+        if end < start {
+            let message = format!("range end is less than range start: {}", range);
+            return Err(ByteRange::invalid_range(message))
+        }
+
+        Ok(ByteRange::Inclusive { start, end })
+    }
+
+    /// Returns inclusive `(start, end)` offsets for a file of the given length,
+    /// or `None` if range is not satisfiable. End is clamped to the last byte of the file.
+    pub fn resolve(&self, file_length: u64) -> Option<(u64, u64)> {
+        if file_length == 0 {
+            return None
+        }
+        let last_byte = file_length - 1;
+
+        match *self {
+            ByteRange::Inclusive { start, end } => {
+                if start > last_byte {
+                    return None
+                }
+                Some((start, end.min(last_byte)))
+            }
+            ByteRange::From { start } => {
+                if start > last_byte {
+                    return None
+                }
+                Some((start, last_byte))
+            }
+            ByteRange::Suffix { length } => {
+                if length == 0 {
+                    return None
+                }
+                Some((file_length - length.min(file_length), last_byte))
+            }
+        }
+    }
+
+    /// Resolves ranges against the file length, skips unsatisfiable ones and merges overlapping or adjacent ranges.
+    /// Result is sorted by start offset.
+    pub fn resolve_and_coalesce(range_list: &[ByteRange], file_length: u64) -> Vec<(u64, u64)> {
+        let mut resolved : Vec<(u64, u64)> = range_list
+            .iter()
+            .filter_map(|range| range.resolve(file_length))
+            .collect();
+        resolved.sort();
+
+        let mut coalesced : Vec<(u64, u64)> = vec![];
+        for (start, end) in resolved {
+            if let Some(last) = coalesced.last_mut() {
+                if start <= last.1.saturating_add(1) {
+                    last.1 = last.1.max(end);
+                    continue;
+                }
+            }
+            coalesced.push((start, end));
+        }
+        coalesced
+    }
+
+    /// Builds `multipart/byteranges` response body. `content_type` is the type of the file itself.
+    pub fn multipart_body(range_content_list: &[ByteRangeContent], file_length: u64, content_type: &str, boundary: &str) -> Vec<u8> {
+        let mut body = vec![];
+        for range_content in range_content_list {
+            let part_header = format!(
+                "--{}{}Content-Type: {}{}Content-Range: {} {}-{}/{}{}{}",
+                boundary,
+                SYMBOL.new_line_carriage_return,
+                content_type,
+                SYMBOL.new_line_carriage_return,
+                ByteRange::UNIT,
+                range_content.start,
+                range_content.end,
+                file_length,
+                SYMBOL.new_line_carriage_return,
+                SYMBOL.new_line_carriage_return,
+            );
+            body.extend_from_slice(part_header.as_bytes());
+            body.extend_from_slice(&range_content.content);
+            body.extend_from_slice(SYMBOL.new_line_carriage_return.as_bytes());
+        }
+        let closing_boundary = format!("--{}--{}", boundary, SYMBOL.new_line_carriage_return);
+        body.extend_from_slice(closing_boundary.as_bytes());
+        body
+    }
+
+    fn invalid_range(message: String) -> FileExtError {
+        FileExtError::from_kind(Operation::Read, SYMBOL.empty_string, ErrorKind::InvalidInput, message)
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::ErrorKind;
+use crate::byte_range::{ByteRange, ByteRangeContent};
+
+#[test]
+fn parse_header() {
+    let range_list = ByteRange::parse_header("bytes=0-99, 200-299,-500,1000-").unwrap();
+    let expected_range_list = vec![
+        ByteRange::Inclusive { start: 0, end: 99 },
+        ByteRange::Inclusive { start: 200, end: 299 },
+        ByteRange::Suffix { length: 500 },
+        ByteRange::From { start: 1000 },
+    ];
+    assert_eq!(expected_range_list, range_list);
+}
+
+#[test]
+fn parse_header_invalid() {
+    let invalid_header_list = [
+        "0-99",
+        "items=0-99",
+        "bytes=",
+        "bytes=abc",
+        "bytes=10-5",
+        "bytes=-",
+        "bytes=a-5",
+        "bytes=5-b",
+    ];
+
+    for header in invalid_header_list {
+        let boxed_parse = ByteRange::parse_header(header);
+        assert!(boxed_parse.is_err(), "{}", header);
+        assert_eq!(ErrorKind::InvalidInput, boxed_parse.err().unwrap().kind());
+    }
+}
+
+#[test]
+fn resolve() {
+    let file_length = 1000;
+
+    assert_eq!(Some((0, 99)), ByteRange::Inclusive { start: 0, end: 99 }.resolve(file_length));
+    assert_eq!(Some((900, 999)), ByteRange::Inclusive { start: 900, end: 5000 }.resolve(file_length));
+    assert_eq!(None, ByteRange::Inclusive { start: 1000, end: 1100 }.resolve(file_length));
+    assert_eq!(Some((999, 999)), ByteRange::From { start: 999 }.resolve(file_length));
+    assert_eq!(None, ByteRange::From { start: 1000 }.resolve(file_length));
+    assert_eq!(Some((500, 999)), ByteRange::Suffix { length: 500 }.resolve(file_length));
+    assert_eq!(Some((0, 999)), ByteRange::Suffix { length: 5000 }.resolve(file_length));
+    assert_eq!(None, ByteRange::Suffix { length: 0 }.resolve(file_length));
+    assert_eq!(None, ByteRange::From { start: 0 }.resolve(0));
+}
+
+#[test]
+fn resolve_and_coalesce() {
+    let range_list = ByteRange::parse_header("bytes=500-599,0-99,50-149,150-199,-100,2000-").unwrap();
+    let resolved = ByteRange::resolve_and_coalesce(&range_list, 1000);
+    assert_eq!(vec![(0, 199), (500, 599), (900, 999)], resolved);
+}
+
+#[test]
+fn multipart_body() {
+    let range_content_list = vec![
+        ByteRangeContent { start: 0, end: 3, content: b"0123".to_vec() },
+        ByteRangeContent { start: 8, end: 9, content: b"89".to_vec() },
+    ];
+    let body = ByteRange::multipart_body(&range_content_list, 10, "text/plain", "BOUNDARY");
+    let expected_body = "--BOUNDARY\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-3/10\r\n\r\n0123\r\n--BOUNDARY\r\nContent-Type: text/plain\r\nContent-Range: bytes 8-9/10\r\n\r\n89\r\n--BOUNDARY--\r\n";
+    assert_eq!(expected_body, String::from_utf8(body).unwrap());
+}
@@ -0,0 +1,44 @@
+use crate::ignore_rules::IgnoreRules;
+
+/// What to do with symlinks found while walking the directory tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Copy file or directory symlink points to
+    Follow,
+    /// Create symlink with the same target in the destination
+    CopyLink,
+    /// Ignore symlinks
+    Skip,
+}
+
+/// Options for `copy_directory`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyDirectoryOptions {
+    pub symlink_policy: SymlinkPolicy,
+    /// Replace files which already exist in the destination, otherwise copy fails
+    pub overwrite: bool,
+    /// If block size is None it is set to 100kb
+    pub block_size: Option<u64>,
+    /// Entries matching the rules are not copied
+    pub ignore_rules: IgnoreRules,
+}
+
+/// Result of `copy_directory`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyDirectoryStatus {
+    /// Whole tree is copied, `bytes_copied` is the total size of all files
+    Completed { bytes_copied: u64 },
+    /// Copy was cancelled by the cancel callback, entries after the last copied block are not in the destination
+    Cancelled { bytes_copied: u64 },
+}
+
+impl Default for CopyDirectoryOptions {
+    fn default() -> Self {
+        CopyDirectoryOptions {
+            symlink_policy: SymlinkPolicy::CopyLink,
+            overwrite: false,
+            block_size: None,
+            ignore_rules: IgnoreRules::new(),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::file_ext_error::FileExtError;
+use crate::ignore_rules::IgnoreRules;
+
+#[cfg(test)]
+mod tests;
+
+/// Options for `delete_directory_with_options`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeleteDirectoryOptions {
+    /// Keep deleting remaining entries if some of them can not be deleted, otherwise deletion stops on the first failure
+    pub continue_on_error: bool,
+    /// Directory the tree is moved (renamed) into before deletion, has to exist on the same filesystem
+    /// outside of the deleted tree and is checked against the path policy.
+    /// Once moved, the original path is free even if deletion of the content fails
+    pub staging_directory: Option<String>,
+    /// Entries matching the rules are kept, together with directories they are in.
+    /// Can not be combined with the staging directory
+    pub ignore_rules: IgnoreRules,
+}
+
+/// Result of the directory deletion
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeleteDirectoryReport {
+    /// Number of deleted files, symlinks and directories
+    pub deleted_count: u64,
+    /// Entries which were not deleted, path of each entry is available via `FileExtError::path`
+    pub failure_list: Vec<FileExtError>,
+    /// Number of entries kept because of the ignore rules
+    pub ignored_count: u64,
+    /// Path the tree was moved to, if staging directory was set
+    pub staged_path: Option<String>,
+}
+
+impl DeleteDirectoryReport {
+    pub fn is_complete(&self) -> bool {
+        self.failure_list.is_empty()
+    }
+}
@@ -0,0 +1,13 @@
+use std::io::ErrorKind;
+use crate::delete_directory_options::DeleteDirectoryReport;
+use crate::file_ext_error::{FileExtError, Operation};
+
+#[test]
+fn report_is_complete() {
+    let mut report = DeleteDirectoryReport::default();
+    assert!(report.is_complete());
+
+    let error = FileExtError::from_kind(Operation::Delete, "some/file", ErrorKind::PermissionDenied, "denied".to_string());
+    report.failure_list.push(error);
+    assert!(!report.is_complete());
+}
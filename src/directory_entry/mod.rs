@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use crate::date_time_ext::DateTimeExt;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::ignore_rules::IgnoreRules;
+
+#[cfg(test)]
+mod tests;
+
+/// Type of the directory entry. Entries sorted by kind go in the declaration order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntryKind {
+    Directory,
+    File,
+    Symlink,
+    /// Sockets, pipes, devices and so on
+    Other,
+}
+
+/// File, directory or symlink found in the directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryEntry {
+    pub name: String,
+    /// Path including the listed directory, for example `test/index.html` for entry `index.html` in `test`
+    pub path: String,
+    /// Same as `path`, but file names which are not valid UTF-8 are kept as is instead of replaced by U+FFFD
+    pub os_path: PathBuf,
+    pub kind: EntryKind,
+    /// Set if entry is a symlink, even if it was followed and `kind` describes the target
+    pub is_symlink: bool,
+    pub size: u64,
+    /// Modification timestamp as nanoseconds in Unix epoch, 0 if not available on the platform
+    pub modified: u128,
+    pub readonly: bool,
+    /// Unix permission bits (for example `0o644`), None on other platforms
+    pub mode: Option<u32>,
+    /// Direct children of the listed directory have depth 1
+    pub depth: usize,
+}
+
+/// Field entries are sorted by. Entries with equal field are sorted by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortBy {
+    Name,
+    Size,
+    Modified,
+    Kind,
+}
+
+/// Options for `list_directory_with_options` and `walk_directory`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListDirectoryOptions {
+    pub sort_by: SortBy,
+    pub descending: bool,
+    /// Used only by the walker, None means no limit. Depth 1 means only direct children
+    pub max_depth: Option<usize>,
+    /// Report symlinks as the entries they point to. Walker descends into directory symlinks
+    pub follow_symlinks: bool,
+    /// Entries matching the rules are not listed, walker does not descend into ignored directories
+    pub ignore_rules: IgnoreRules,
+}
+
+impl Default for ListDirectoryOptions {
+    fn default() -> Self {
+        ListDirectoryOptions {
+            sort_by: SortBy::Name,
+            descending: false,
+            max_depth: None,
+            follow_symlinks: false,
+            ignore_rules: IgnoreRules::new(),
+        }
+    }
+}
+
+impl DirectoryEntry {
+    pub(crate) fn from_path(path: &Path, depth: usize, follow_symlinks: bool) -> Result<DirectoryEntry, FileExtError> {
+        let entry_path = path.to_string_lossy().to_string();
+        let boxed_symlink_metadata = fs::symlink_metadata(path);
+        if boxed_symlink_metadata.is_err() {
+            let error = boxed_symlink_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &entry_path, error))
+        }
+
+        let mut metadata = boxed_symlink_metadata.unwrap();
+        let is_symlink = metadata.file_type().is_symlink();
+        if is_symlink && follow_symlinks {
+            // dangling symlink is reported as a symlink
+            if let Ok(target_metadata) = fs::metadata(path) {
+                metadata = target_metadata;
+            }
+        }
+
+        let file_type = metadata.file_type();
+        let kind = if file_type.is_symlink() {
+            EntryKind::Symlink
+        } else if file_type.is_dir() {
+            EntryKind::Directory
+        } else if file_type.is_file() {
+            EntryKind::File
+        } else {
+            EntryKind::Other
+        };
+
+        let modified = match metadata.modified() {
+            Ok(modified) => DateTimeExt::_system_time_to_unix_nanos(modified),
+            Err(_) => 0,
+        };
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        Ok(DirectoryEntry {
+            name,
+            path: entry_path,
+            os_path: path.to_path_buf(),
+            kind,
+            is_symlink,
+            size: metadata.len(),
+            modified,
+            readonly: metadata.permissions().readonly(),
+            mode: DirectoryEntry::mode(&metadata),
+            depth,
+        })
+    }
+
+    #[cfg(target_family = "unix")]
+    fn mode(metadata: &Metadata) -> Option<u32> {
+        use std::os::unix::fs::PermissionsExt;
+        Some(metadata.permissions().mode() & 0o7777)
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    fn mode(_metadata: &Metadata) -> Option<u32> {
+        None
+    }
+
+    pub(crate) fn sort(entry_list: &mut [DirectoryEntry], sort_by: SortBy, descending: bool) {
+        entry_list.sort_by(|a, b| {
+            let ordering = match sort_by {
+                SortBy::Name => Ordering::Equal,
+                SortBy::Size => a.size.cmp(&b.size),
+                SortBy::Modified => a.modified.cmp(&b.modified),
+                SortBy::Kind => a.kind.cmp(&b.kind),
+            };
+            let ordering = ordering.then_with(|| a.name.cmp(&b.name));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
@@ -0,0 +1,45 @@
+use crate::directory_entry::{DirectoryEntry, EntryKind, SortBy};
+
+fn entry(name: &str, kind: EntryKind, size: u64, modified: u128) -> DirectoryEntry {
+    DirectoryEntry {
+        name: name.to_string(),
+        path: name.to_string(),
+        os_path: name.into(),
+        kind,
+        is_symlink: false,
+        size,
+        modified,
+        readonly: false,
+        mode: None,
+        depth: 1,
+    }
+}
+
+fn names(entry_list: &[DirectoryEntry]) -> Vec<&str> {
+    entry_list.iter().map(|entry| entry.name.as_str()).collect()
+}
+
+#[test]
+fn sort() {
+    let mut entry_list = vec![
+        entry("c", EntryKind::File, 10, 3),
+        entry("a", EntryKind::Symlink, 30, 1),
+        entry("b", EntryKind::Directory, 20, 2),
+        entry("d", EntryKind::File, 10, 4),
+    ];
+
+    DirectoryEntry::sort(&mut entry_list, SortBy::Name, false);
+    assert_eq!(vec!["a", "b", "c", "d"], names(&entry_list));
+
+    DirectoryEntry::sort(&mut entry_list, SortBy::Name, true);
+    assert_eq!(vec!["d", "c", "b", "a"], names(&entry_list));
+
+    DirectoryEntry::sort(&mut entry_list, SortBy::Size, false);
+    assert_eq!(vec!["c", "d", "b", "a"], names(&entry_list));
+
+    DirectoryEntry::sort(&mut entry_list, SortBy::Modified, true);
+    assert_eq!(vec!["d", "c", "b", "a"], names(&entry_list));
+
+    DirectoryEntry::sort(&mut entry_list, SortBy::Kind, false);
+    assert_eq!(vec!["b", "c", "d", "a"], names(&entry_list));
+}
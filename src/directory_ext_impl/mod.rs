@@ -1,141 +1,734 @@
 use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use crate::copy_directory_options::{CopyDirectoryOptions, CopyDirectoryStatus, SymlinkPolicy};
+use crate::date_time_ext::DateTimeExt;
+use crate::delete_directory_options::{DeleteDirectoryOptions, DeleteDirectoryReport};
+use crate::directory_entry::{DirectoryEntry, EntryKind, ListDirectoryOptions};
+use crate::directory_walker::DirectoryWalker;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::file_ext_impl::FileExtImpl;
+use crate::file_range::FileRange;
 use crate::filter_string::FilterString;
+use crate::ignore_rules::IgnoreRules;
 use crate::path_ext_impl::PathExtImpl;
-use crate::symbol::SYMBOL;
+use crate::temp_entry;
+use crate::temp_entry::TempDirectory;
 
 #[cfg(test)]
 mod tests;
 
 pub struct DirectoryExtImpl;
 
+/// Entry of the directory tree to be copied, path is relative to the source directory
+enum CopyEntry {
+    Directory(PathBuf),
+    File(PathBuf, PathBuf, u64),
+    Symlink(PathBuf, PathBuf),
+}
+
 impl DirectoryExtImpl {
-    pub fn does_directory_exist(path: &str) -> bool {
-        let file_exists = Path::new(path).is_dir();
+    pub fn does_directory_exist<P: AsRef<Path>>(path: P) -> bool {
+        let file_exists = path.as_ref().is_dir();
         file_exists
     }
 
-    pub fn create_directory(path: &str) -> Result<(), String> {
-        let boxed_check = FilterString::is_valid_input_string(path);
+    pub fn create_directory<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        let path = path.as_ref();
+        let directory_path = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::CreateDirectory, &directory_path, message))
+        }
+
+        if fs::symlink_metadata(path).is_ok() {
+            let message = format!("There is already a file or directory at the given path: {}", directory_path);
+            return Err(FileExtError::from_kind(Operation::CreateDirectory, &directory_path, ErrorKind::AlreadyExists, message))
+        }
+
+        let boxed_ensure = DirectoryExtImpl::ensure_directory_path(path, None);
+        if boxed_ensure.is_err() {
+            let error = boxed_ensure.err().unwrap();
+            return Err(error)
+        }
+
+        Ok(())
+    }
+
+    pub fn ensure_directory(path: &str, mode: Option<u32>) -> Result<Vec<String>, FileExtError> {
+        let boxed_ensure = DirectoryExtImpl::ensure_directory_path(path, mode);
+        if boxed_ensure.is_err() {
+            let error = boxed_ensure.err().unwrap();
+            return Err(error)
         }
 
-        DirectoryExtImpl::recursively_create_directories("", path)
+        // every created directory is a prefix of the given path, so it is valid UTF-8 as well
+        let created_list = boxed_ensure.unwrap()
+            .iter()
+            .map(|created| created.to_string_lossy().to_string())
+            .collect();
+        Ok(created_list)
     }
 
-    pub fn delete_directory(path: &str) -> Result<(), String> {
-        let boxed_check = FilterString::is_valid_input_string(path);
+    /// Creates the directory and all of its missing parents, existing ones are left as is.
+    /// Returns created directories, starting from the outermost one. If creation fails,
+    /// directories created so far are removed
+    pub fn ensure_directory_path<P: AsRef<Path>>(path: P, mode: Option<u32>) -> Result<Vec<PathBuf>, FileExtError> {
+        let path = path.as_ref();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::CreateDirectory, &path.to_string_lossy(), message))
         }
 
-        if !DirectoryExtImpl::does_directory_exist(path) {
-            let message = format!("There is no directory at the given path: {}", path);
-            return Err(message)
+        let mut missing_list = vec![];
+        for ancestor in path.ancestors() {
+            if ancestor.as_os_str().is_empty() || ancestor.is_dir() {
+                break;
+            }
+            missing_list.push(ancestor);
+        }
+        missing_list.reverse();
+
+        let mut created_list : Vec<PathBuf> = vec![];
+        for directory in missing_list {
+            let boxed_create = DirectoryExtImpl::create_single_directory(directory, mode);
+            if boxed_create.is_err() {
+                let error = boxed_create.err().unwrap();
+                // directory might be created concurrently, it is fine as long as it is a directory
+                if error.kind() == ErrorKind::AlreadyExists && directory.is_dir() {
+                    continue;
+                }
+
+                for created in created_list.iter().rev() {
+                    let _ = fs::remove_dir(created);
+                }
+                return Err(FileExtError::from_io(Operation::CreateDirectory, &directory.to_string_lossy(), error))
+            }
+            created_list.push(directory.to_path_buf());
         }
 
-        DirectoryExtImpl::remove_directory_recursively_bypass_warnings(path)
+        Ok(created_list)
     }
 
-    #[cfg(target_family = "windows")]
-    fn remove_directory_recursively_bypass_warnings(path: &str) -> Result<(), String> {
+    /// Creates a new directory with a unique name in the temp folder, accessible only by the owner on Unix
+    pub fn create_temp_directory(prefix: &str) -> Result<TempDirectory, FileExtError> {
+        let boxed_temp_folder = PathExtImpl::get_temp_folder_path();
+        if boxed_temp_folder.is_err() {
+            let error = boxed_temp_folder.err().unwrap();
+            return Err(error)
+        }
+        let temp_folder = boxed_temp_folder.unwrap();
+
+        let boxed_check = temp_entry::check_affix(Operation::CreateDirectory, prefix);
+        if boxed_check.is_err() {
+            let error = boxed_check.err().unwrap();
+            return Err(error)
+        }
 
-        let boxed_rm_rf = Command::new("cmd")
-            .args(["/c", "rd" ,"/s", "/q", path])
-            .output();
+        for _attempt in 0..temp_entry::MAX_ATTEMPT_COUNT {
+            let path = temp_entry::random_path(&temp_folder, prefix, "");
+            let boxed_create = DirectoryExtImpl::create_single_directory(Path::new(&path), Some(0o700));
+            if boxed_create.is_err() {
+                let error = boxed_create.err().unwrap();
+                if error.kind() == ErrorKind::AlreadyExists {
+                    continue;
+                }
+                return Err(FileExtError::from_io(Operation::CreateDirectory, &path, error))
+            }
 
-        if boxed_rm_rf.is_err() {
-            let message = boxed_rm_rf.err().unwrap().to_string();
-            return Err(message)
+            return Ok(TempDirectory::new(path))
         }
 
-        let output = boxed_rm_rf.unwrap();
+        let message = format!("unable to find unique directory name after {} attempts", temp_entry::MAX_ATTEMPT_COUNT);
+        Err(FileExtError::from_kind(Operation::CreateDirectory, &temp_folder, ErrorKind::AlreadyExists, message))
+    }
+
+    #[cfg(target_family = "unix")]
+    fn create_single_directory(directory: &Path, mode: Option<u32>) -> std::io::Result<()> {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        if mode.is_none() {
+            return fs::create_dir(directory)
+        }
+
+        let mode = mode.unwrap();
+        let boxed_create = fs::DirBuilder::new().mode(mode).create(directory);
+        if boxed_create.is_err() {
+            let error = boxed_create.err().unwrap();
+            return Err(error)
+        }
+
+        // mode given to the builder is reduced by umask, set it explicitly
+        fs::set_permissions(directory, fs::Permissions::from_mode(mode))
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    fn create_single_directory(directory: &Path, _mode: Option<u32>) -> std::io::Result<()> {
+        fs::create_dir(directory)
+    }
 
-        let success = output.status.success();
-        if !success {
-            let stdout = String::from_utf8(output.stdout).unwrap();
-            let stderr = String::from_utf8(output.stderr).unwrap();
-            let log = [stdout, stderr].join(SYMBOL.new_line_carriage_return);
+    pub fn delete_directory<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        let path = path.as_ref();
+        let directory_path = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::DeleteDirectory, &directory_path, message))
+        }
 
-            return Err(log);
+        let boxed_report = DirectoryExtImpl::delete_directory_with_options(path, &DeleteDirectoryOptions::default());
+        if boxed_report.is_err() {
+            let error = boxed_report.err().unwrap();
+            return Err(error)
         }
 
         Ok(())
     }
 
-    #[cfg(target_family = "unix")]
-    fn remove_directory_recursively_bypass_warnings(path: &str) -> Result<(), String> {
+    pub fn delete_directory_with_options<P: AsRef<Path>>(path: P, options: &DeleteDirectoryOptions) -> Result<DeleteDirectoryReport, FileExtError> {
+        let path = path.as_ref();
+        let directory_path = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::DeleteDirectory, &directory_path, message))
+        }
 
-        let boxed_rm_rf = Command::new("rm")
-            .args(["-Rf", path])
-            .output();
+        // symlink to a directory is removed itself, the directory it points to is never touched
+        let is_symlink = fs::symlink_metadata(path).map(|metadata| metadata.file_type().is_symlink()).unwrap_or(false);
+        if is_symlink && path.is_dir() {
+            let boxed_remove = DirectoryExtImpl::remove_symlink(path);
+            if boxed_remove.is_err() {
+                let error = boxed_remove.err().unwrap();
+                return Err(FileExtError::from_io(Operation::DeleteDirectory, &directory_path, error))
+            }
+            return Ok(DeleteDirectoryReport { deleted_count: 1, ..DeleteDirectoryReport::default() })
+        }
 
-        if boxed_rm_rf.is_err() {
-            let message = boxed_rm_rf.err().unwrap().to_string();
-            return Err(message)
+        let is_directory = fs::symlink_metadata(path).map(|metadata| metadata.is_dir()).unwrap_or(false);
+        if !is_directory {
+            let message = format!("There is no directory at the given path: {}", directory_path);
+            return Err(FileExtError::from_kind(Operation::DeleteDirectory, &directory_path, ErrorKind::NotFound, message))
         }
 
-        let output = boxed_rm_rf.unwrap();
+        if options.staging_directory.is_some() && !options.ignore_rules.is_empty() {
+            let message = "ignore rules can not be combined with the staging directory".to_string();
+            return Err(FileExtError::from_kind(Operation::DeleteDirectory, &directory_path, ErrorKind::InvalidInput, message))
+        }
 
-        let success = output.status.success();
-        if !success {
-            let stdout = String::from_utf8(output.stdout).unwrap();
-            let stderr = String::from_utf8(output.stderr).unwrap();
-            let log = [stdout, stderr].join(SYMBOL.new_line_carriage_return);
+        let mut report = DeleteDirectoryReport::default();
+        let mut tree_path = PathBuf::from(path);
 
-            return Err(log);
+        if let Some(staging_directory) = &options.staging_directory {
+            let boxed_check = DirectoryExtImpl::check_staging_directory(path, staging_directory);
+            if boxed_check.is_err() {
+                let error = boxed_check.err().unwrap();
+                return Err(error)
+            }
+
+            let mut staged_name = tree_path.file_name().unwrap_or_default().to_os_string();
+            staged_name.push(format!(".{}.{}.deleted", std::process::id(), DateTimeExt::_now_unix_epoch_nanos()));
+            let staged_path = Path::new(staging_directory).join(staged_name);
+
+            let boxed_rename = fs::rename(path, &staged_path);
+            if boxed_rename.is_err() {
+                let error = boxed_rename.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Rename, &directory_path, error))
+            }
+
+            report.staged_path = Some(staged_path.to_string_lossy().to_string());
+            tree_path = staged_path;
+        }
+
+        let mut ignore_rules = options.ignore_rules.clone();
+        let boxed_remove = DirectoryExtImpl::remove_tree(&tree_path, Path::new(""), options, &mut ignore_rules, &mut report);
+        if boxed_remove.is_err() {
+            let error = boxed_remove.err().unwrap();
+            return Err(error)
+        }
+
+        Ok(report)
+    }
+
+    /// Staging directory has to pass the path policy and must not be the deleted tree or inside of it
+    fn check_staging_directory(path: &Path, staging_directory: &str) -> Result<(), FileExtError> {
+        let boxed_check = FilterString::is_valid_input_string(staging_directory);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::DeleteDirectory, staging_directory, message))
+        }
+
+        let boxed_staging = fs::canonicalize(staging_directory);
+        if boxed_staging.is_err() {
+            let error = boxed_staging.err().unwrap();
+            return Err(FileExtError::from_io(Operation::DeleteDirectory, staging_directory, error))
+        }
+
+        let boxed_tree = fs::canonicalize(path);
+        if boxed_tree.is_err() {
+            let error = boxed_tree.err().unwrap();
+            return Err(FileExtError::from_io(Operation::DeleteDirectory, &path.to_string_lossy(), error))
+        }
+
+        if boxed_staging.unwrap().starts_with(boxed_tree.unwrap()) {
+            let message = format!("staging directory can not be inside the deleted directory: {}", staging_directory);
+            return Err(FileExtError::from_kind(Operation::DeleteDirectory, staging_directory, ErrorKind::InvalidInput, message))
         }
 
         Ok(())
     }
 
-    // WASI guests have no subprocess model to shell out to `rm` with (unlike
-    // the unix branch above) — `fs::remove_dir_all` is a plain wasi:filesystem
-    // call and does the same job directly.
-    #[cfg(target_family = "wasm")]
-    fn remove_directory_recursively_bypass_warnings(path: &str) -> Result<(), String> {
-        fs::remove_dir_all(path).map_err(|e| e.to_string())
+    pub fn list_directory<P: AsRef<Path>>(path: P, options: &ListDirectoryOptions) -> Result<Vec<DirectoryEntry>, FileExtError> {
+        let path = path.as_ref();
+        let boxed_check = DirectoryExtImpl::check_listed_directory(path);
+        if boxed_check.is_err() {
+            let error = boxed_check.err().unwrap();
+            return Err(error)
+        }
+
+        let mut ignore_rules = options.ignore_rules.clone();
+        let boxed_load = ignore_rules.load_ignore_files(path, "");
+        if boxed_load.is_err() {
+            let error = boxed_load.err().unwrap();
+            return Err(error)
+        }
+
+        let boxed_entry_list = DirectoryExtImpl::read_entries(path, 1, options);
+        if boxed_entry_list.is_err() {
+            let error = boxed_entry_list.err().unwrap();
+            return Err(error)
+        }
+
+        let mut entry_list = boxed_entry_list.unwrap();
+        entry_list.retain(|entry| !ignore_rules.is_ignored(&entry.name, entry.kind == EntryKind::Directory));
+        Ok(entry_list)
     }
 
-    fn recursively_create_directories(processed_path: &str, remaining_path: &str) -> Result<(), String> {
-        let boxed_split = remaining_path.split_once(PathExtImpl::get_path_separator().as_str());
-        if boxed_split.is_none() {
-            let mut folder_path = remaining_path.to_string();
-            if processed_path.chars().count() != 0 {
-                folder_path = [processed_path, remaining_path].join(PathExtImpl::get_path_separator().as_str());
+    pub fn walk_directory<P: AsRef<Path>>(path: P, options: &ListDirectoryOptions) -> Result<DirectoryWalker, FileExtError> {
+        let path = path.as_ref();
+        let boxed_check = DirectoryExtImpl::check_listed_directory(path);
+        if boxed_check.is_err() {
+            let error = boxed_check.err().unwrap();
+            return Err(error)
+        }
+
+        DirectoryWalker::new(path, options.clone())
+    }
+
+    fn check_listed_directory(path: &Path) -> Result<(), FileExtError> {
+        let directory_path = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::ListDirectory, &directory_path, message))
+        }
+
+        if !DirectoryExtImpl::does_directory_exist(path) {
+            let message = format!("There is no directory at the given path: {}", directory_path);
+            return Err(FileExtError::from_kind(Operation::ListDirectory, &directory_path, ErrorKind::NotFound, message))
+        }
+
+        Ok(())
+    }
+
+    /// Lists entries of the directory, sorted according to the options
+    pub(crate) fn read_entries(directory: &Path, depth: usize, options: &ListDirectoryOptions) -> Result<Vec<DirectoryEntry>, FileExtError> {
+        let directory_path = directory.to_string_lossy().to_string();
+        let boxed_read_dir = fs::read_dir(directory);
+        if boxed_read_dir.is_err() {
+            let error = boxed_read_dir.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ListDirectory, &directory_path, error))
+        }
+
+        let mut entry_list = vec![];
+        for boxed_dir_entry in boxed_read_dir.unwrap() {
+            if boxed_dir_entry.is_err() {
+                let error = boxed_dir_entry.err().unwrap();
+                return Err(FileExtError::from_io(Operation::ListDirectory, &directory_path, error))
             }
 
+            let boxed_entry = DirectoryEntry::from_path(&boxed_dir_entry.unwrap().path(), depth, options.follow_symlinks);
+            if boxed_entry.is_err() {
+                let error = boxed_entry.err().unwrap();
+                return Err(error)
+            }
+            entry_list.push(boxed_entry.unwrap());
+        }
+
+        DirectoryEntry::sort(&mut entry_list, options.sort_by, options.descending);
+        Ok(entry_list)
+    }
+
+    pub fn copy_directory
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        options: &CopyDirectoryOptions,
+        mut progress_callback: F,
+        mut cancel_callback: C,
+    )
+        -> Result<CopyDirectoryStatus, FileExtError> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        for path in [from, to] {
+            let boxed_check = FilterString::is_valid_input_path(path);
+            if boxed_check.is_err() {
+                let message = boxed_check.err().unwrap();
+                return Err(FileExtError::invalid_path(Operation::CopyDirectory, &path.to_string_lossy(), message))
+            }
+        }
+
+        if !DirectoryExtImpl::does_directory_exist(from) {
+            let from_path = from.to_string_lossy();
+            let message = format!("There is no directory at the given path: {}", from_path);
+            return Err(FileExtError::from_kind(Operation::CopyDirectory, &from_path, ErrorKind::NotFound, message))
+        }
+
+        // whole tree is listed before anything is created, so copying into a subdirectory of the source does not recurse into the copy
+        let mut entry_list = vec![];
+        let mut ancestor_list = vec![];
+        let mut ignore_rules = options.ignore_rules.clone();
+        let boxed_walk = DirectoryExtImpl::list_copy_entries(from, PathBuf::new(), options.symlink_policy, &mut ignore_rules, &mut ancestor_list, &mut entry_list);
+        if boxed_walk.is_err() {
+            let error = boxed_walk.err().unwrap();
+            return Err(error)
+        }
+
+        let total : u64 = entry_list.iter().map(|entry| match entry {
+            CopyEntry::File(_, _, length) => *length,
+            _ => 0,
+        }).sum();
+
+        let boxed_create = fs::create_dir_all(to);
+        if boxed_create.is_err() {
+            let error = boxed_create.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CreateDirectory, &to.to_string_lossy(), error))
+        }
+
+        let destination_root = to;
+        let mut copied = 0;
+        let mut cancelled = false;
+        let mut copied_before_cancel = 0;
+        for entry in entry_list {
+            match entry {
+                CopyEntry::Directory(relative_path) => {
+                    let destination = destination_root.join(relative_path);
+                    let boxed_create = fs::create_dir_all(&destination);
+                    if boxed_create.is_err() {
+                        let error = boxed_create.err().unwrap();
+                        return Err(FileExtError::from_io(Operation::CreateDirectory, &destination.to_string_lossy(), error))
+                    }
+                }
+                CopyEntry::Symlink(relative_path, points_to) => {
+                    let destination = destination_root.join(relative_path);
+                    let boxed_symlink = DirectoryExtImpl::copy_symlink(&points_to, &destination, options.overwrite);
+                    if boxed_symlink.is_err() {
+                        let error = boxed_symlink.err().unwrap();
+                        return Err(error)
+                    }
+                }
+                CopyEntry::File(relative_path, source, length) => {
+                    let destination = destination_root.join(relative_path);
+                    let destination_path = destination.to_string_lossy().to_string();
+                    if fs::symlink_metadata(&destination).is_ok() {
+                        if !options.overwrite {
+                            let message = format!("There is a file on a given path: {}", destination_path);
+                            return Err(FileExtError::from_kind(Operation::CopyDirectory, &destination_path, ErrorKind::AlreadyExists, message))
+                        }
+
+                        let boxed_delete = FileExtImpl::delete_file(&destination);
+                        if boxed_delete.is_err() {
+                            let error = boxed_delete.err().unwrap();
+                            return Err(error)
+                        }
+                    }
+
+                    // paths are passed as is, file names which are not valid UTF-8 are copied unchanged
+                    let offset = copied;
+                    let boxed_copy = FileExtImpl::copy_path_range_with_callbacks(
+                        &source,
+                        &destination,
+                        FileRange::whole(length),
+                        options.block_size,
+                        |start, end, _| progress_callback(offset + start, offset + end, total),
+                        |start, end, _| {
+                            cancelled = cancel_callback(offset + start, offset + end, total);
+                            copied_before_cancel = offset + end + 1;
+                            cancelled
+                        },
+                    );
+                    if boxed_copy.is_err() {
+                        let error = boxed_copy.err().unwrap();
+                        return Err(error)
+                    }
+
+                    if cancelled {
+                        return Ok(CopyDirectoryStatus::Cancelled { bytes_copied: copied_before_cancel })
+                    }
+                    copied += length;
+                }
+            }
+        }
+
+        Ok(CopyDirectoryStatus::Completed { bytes_copied: copied })
+    }
+
+    fn list_copy_entries(
+        directory: &Path,
+        relative_directory: PathBuf,
+        symlink_policy: SymlinkPolicy,
+        ignore_rules: &mut IgnoreRules,
+        ancestor_list: &mut Vec<PathBuf>,
+        entry_list: &mut Vec<CopyEntry>,
+    ) -> Result<(), FileExtError> {
+        let directory_path = directory.to_string_lossy().to_string();
+        let boxed_canonical = fs::canonicalize(directory);
+        if boxed_canonical.is_err() {
+            let error = boxed_canonical.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CopyDirectory, &directory_path, error))
+        }
+
+        let canonical = boxed_canonical.unwrap();
+        if ancestor_list.contains(&canonical) {
+            let message = format!("symlink loop detected at {}", directory_path);
+            return Err(FileExtError::from_kind(Operation::CopyDirectory, &directory_path, ErrorKind::InvalidData, message))
+        }
+        ancestor_list.push(canonical);
+
+        let boxed_load = ignore_rules.load_ignore_files(directory, &relative_directory.to_string_lossy());
+        if boxed_load.is_err() {
+            let error = boxed_load.err().unwrap();
+            return Err(error)
+        }
+
+        let boxed_read_dir = fs::read_dir(directory);
+        if boxed_read_dir.is_err() {
+            let error = boxed_read_dir.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CopyDirectory, &directory_path, error))
+        }
+
+        let mut child_list = vec![];
+        for boxed_entry in boxed_read_dir.unwrap() {
+            if boxed_entry.is_err() {
+                let error = boxed_entry.err().unwrap();
+                return Err(FileExtError::from_io(Operation::CopyDirectory, &directory_path, error))
+            }
+            child_list.push(boxed_entry.unwrap().path());
+        }
+        child_list.sort();
+
+        for child in child_list {
+            let relative_path = relative_directory.join(child.file_name().unwrap_or_default());
+            let child_path = child.to_string_lossy().to_string();
+
+            let boxed_metadata = fs::symlink_metadata(&child);
+            if boxed_metadata.is_err() {
+                let error = boxed_metadata.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Metadata, &child_path, error))
+            }
+            let mut metadata = boxed_metadata.unwrap();
+
+            let is_directory = metadata.is_dir() || (symlink_policy == SymlinkPolicy::Follow && child.is_dir());
+            if ignore_rules.is_ignored(&relative_path.to_string_lossy(), is_directory) {
+                continue;
+            }
+
+            if metadata.file_type().is_symlink() {
+                match symlink_policy {
+                    SymlinkPolicy::Skip => continue,
+                    SymlinkPolicy::CopyLink => {
+                        let boxed_points_to = fs::read_link(&child);
+                        if boxed_points_to.is_err() {
+                            let error = boxed_points_to.err().unwrap();
+                            return Err(FileExtError::from_io(Operation::ReadSymlink, &child_path, error))
+                        }
+                        entry_list.push(CopyEntry::Symlink(relative_path, boxed_points_to.unwrap()));
+                        continue;
+                    }
+                    SymlinkPolicy::Follow => {
+                        let boxed_target_metadata = fs::metadata(&child);
+                        if boxed_target_metadata.is_err() {
+                            let error = boxed_target_metadata.err().unwrap();
+                            return Err(FileExtError::from_io(Operation::Metadata, &child_path, error))
+                        }
+                        metadata = boxed_target_metadata.unwrap();
+                    }
+                }
+            }
+
+            if metadata.is_dir() {
+                entry_list.push(CopyEntry::Directory(relative_path.clone()));
+                let boxed_walk = DirectoryExtImpl::list_copy_entries(&child, relative_path, symlink_policy, ignore_rules, ancestor_list, entry_list);
+                if boxed_walk.is_err() {
+                    let error = boxed_walk.err().unwrap();
+                    return Err(error)
+                }
+            } else if metadata.is_file() {
+                entry_list.push(CopyEntry::File(relative_path, child, metadata.len()));
+            }
+        }
 
-            let boxed_create_folder = fs::create_dir(folder_path.as_str());
-            if boxed_create_folder.is_err() {
-                let message = boxed_create_folder.err().unwrap().to_string();
-                return Err(message)
+        ancestor_list.pop();
+        Ok(())
+    }
+
+    fn copy_symlink(points_to: &Path, destination: &Path, overwrite: bool) -> Result<(), FileExtError> {
+        let destination_path = destination.to_string_lossy().to_string();
+        if fs::symlink_metadata(destination).is_ok() {
+            if !overwrite {
+                let message = format!("There is a file on a given path: {}", destination_path);
+                return Err(FileExtError::from_kind(Operation::CopyDirectory, &destination_path, ErrorKind::AlreadyExists, message))
             }
 
-            return Ok(());
+            let boxed_delete = fs::remove_file(destination);
+            if boxed_delete.is_err() {
+                let error = boxed_delete.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Delete, &destination_path, error))
+            }
+        }
+
+        #[cfg(target_family = "unix")]
+        let boxed_symlink = std::os::unix::fs::symlink(points_to, destination);
+
+        #[cfg(target_family = "windows")]
+        let boxed_symlink = {
+            let target = destination.parent().unwrap_or(Path::new("")).join(points_to);
+            if target.is_dir() {
+                std::os::windows::fs::symlink_dir(points_to, destination)
+            } else {
+                std::os::windows::fs::symlink_file(points_to, destination)
+            }
+        };
+
+        #[cfg(target_family = "wasm")]
+        let boxed_symlink : std::io::Result<()> = Err(std::io::Error::new(ErrorKind::Unsupported, "symlinks are not supported"));
+
+        if boxed_symlink.is_err() {
+            let error = boxed_symlink.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CreateSymlink, &destination_path, error))
+        }
+        Ok(())
+    }
+
+    /// Removes entries of the directory and the directory itself. Symlinks are removed, never followed,
+    /// so nothing outside of the tree is touched. Directory with ignored entries is kept
+    fn remove_tree(
+        path: &Path,
+        relative_path: &Path,
+        options: &DeleteDirectoryOptions,
+        ignore_rules: &mut IgnoreRules,
+        report: &mut DeleteDirectoryReport,
+    ) -> Result<(), FileExtError> {
+        let directory_path = path.to_string_lossy().to_string();
+        let boxed_load = ignore_rules.load_ignore_files(path, &relative_path.to_string_lossy());
+        if boxed_load.is_err() {
+            let error = boxed_load.err().unwrap();
+            return DirectoryExtImpl::delete_failure(error, options, report)
         }
-        let (folder, remaining_path) = boxed_split.unwrap();
 
-        let mut  folder_path = folder.to_string();
-        if processed_path.chars().count() != 0 {
-            folder_path = [processed_path, folder].join(PathExtImpl::get_path_separator().as_str());
+        let ignored_count = report.ignored_count;
+        let boxed_read_dir = fs::read_dir(path);
+        if boxed_read_dir.is_err() {
+            let error = boxed_read_dir.err().unwrap();
+            return DirectoryExtImpl::delete_failure(FileExtError::from_io(Operation::DeleteDirectory, &directory_path, error), options, report)
         }
 
+        for boxed_entry in boxed_read_dir.unwrap() {
+            if boxed_entry.is_err() {
+                let error = boxed_entry.err().unwrap();
+                let boxed_failure = DirectoryExtImpl::delete_failure(FileExtError::from_io(Operation::DeleteDirectory, &directory_path, error), options, report);
+                if boxed_failure.is_err() {
+                    let error = boxed_failure.err().unwrap();
+                    return Err(error)
+                }
+                continue;
+            }
 
-        let boxed_create_folder = fs::create_dir(folder_path.as_str());
-        if boxed_create_folder.is_err() {
-            let message = boxed_create_folder.err().unwrap().to_string();
-            return Err(message)
+            let child = boxed_entry.unwrap().path();
+            let child_relative_path = relative_path.join(child.file_name().unwrap_or_default());
+            let boxed_remove = DirectoryExtImpl::remove_entry(&child, &child_relative_path, options, ignore_rules, report);
+            if boxed_remove.is_err() {
+                let error = boxed_remove.err().unwrap();
+                return Err(error)
+            }
         }
-        let mut _processed_path = folder.to_string();
-        if processed_path.chars().count() != 0 {
-            _processed_path = [processed_path, folder].join(PathExtImpl::get_path_separator().as_str());
+
+        if report.ignored_count > ignored_count {
+            return Ok(())
         }
-        DirectoryExtImpl::recursively_create_directories(_processed_path.as_str(), remaining_path)
+
+        let boxed_remove_dir = fs::remove_dir(path);
+        if boxed_remove_dir.is_err() {
+            let error = boxed_remove_dir.err().unwrap();
+            return DirectoryExtImpl::delete_failure(FileExtError::from_io(Operation::DeleteDirectory, &directory_path, error), options, report)
+        }
+        report.deleted_count += 1;
+
+        Ok(())
+    }
+
+    fn remove_entry(
+        path: &Path,
+        relative_path: &Path,
+        options: &DeleteDirectoryOptions,
+        ignore_rules: &mut IgnoreRules,
+        report: &mut DeleteDirectoryReport,
+    ) -> Result<(), FileExtError> {
+        let entry_path = path.to_string_lossy().to_string();
+        let boxed_metadata = fs::symlink_metadata(path);
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return DirectoryExtImpl::delete_failure(FileExtError::from_io(Operation::Metadata, &entry_path, error), options, report)
+        }
+
+        let file_type = boxed_metadata.unwrap().file_type();
+        if ignore_rules.is_ignored(&relative_path.to_string_lossy(), file_type.is_dir()) {
+            report.ignored_count += 1;
+            return Ok(())
+        }
+
+        if file_type.is_dir() {
+            return DirectoryExtImpl::remove_tree(path, relative_path, options, ignore_rules, report)
+        }
+
+        let boxed_remove = if file_type.is_symlink() {
+            DirectoryExtImpl::remove_symlink(path)
+        } else {
+            fs::remove_file(path)
+        };
+        if boxed_remove.is_err() {
+            let error = boxed_remove.err().unwrap();
+            return DirectoryExtImpl::delete_failure(FileExtError::from_io(Operation::Delete, &entry_path, error), options, report)
+        }
+        report.deleted_count += 1;
+
+        Ok(())
+    }
+
+    #[cfg(target_family = "windows")]
+    fn remove_symlink(path: &Path) -> std::io::Result<()> {
+        // directory symlinks on windows are removed as directories, target is left untouched
+        let boxed_remove = fs::remove_file(path);
+        if boxed_remove.is_err() {
+            return fs::remove_dir(path)
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_family = "windows"))]
+    fn remove_symlink(path: &Path) -> std::io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn delete_failure(error: FileExtError, options: &DeleteDirectoryOptions, report: &mut DeleteDirectoryReport) -> Result<(), FileExtError> {
+        if !options.continue_on_error {
+            return Err(error)
+        }
+        report.failure_list.push(error);
+        Ok(())
     }
 }
 
@@ -1,6 +1,14 @@
+use std::io::ErrorKind;
+use std::path::Path;
+use crate::copy_directory_options::{CopyDirectoryOptions, CopyDirectoryStatus, SymlinkPolicy};
+use crate::delete_directory_options::DeleteDirectoryOptions;
 use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::file_ext_impl::FileExtImpl;
+use crate::ignore_rules::IgnoreRules;
 use crate::FileExt;
 use crate::path_ext_impl::PathExtImpl;
+use crate::write_mode::WriteMode;
 
 #[test]
 fn new_directory_recursively_create_delete() {
@@ -87,8 +95,13 @@ fn delete_nonexistent_directory() {
     let boxed_delete = DirectoryExtImpl::delete_directory(path);
     assert!(boxed_delete.is_err());
 
-    let expected_error = format!("There is no directory at the given path: {}", path);
-    assert_eq!(expected_error, boxed_delete.err().unwrap())
+    let error = boxed_delete.err().unwrap();
+    assert_eq!(ErrorKind::NotFound, error.kind());
+    assert_eq!(Operation::DeleteDirectory, error.operation());
+    assert_eq!(path, error.path());
+
+    let expected_message = format!("There is no directory at the given path: {}", path);
+    assert_eq!(expected_message, error.message())
 }
 
 #[test]
@@ -100,8 +113,12 @@ fn delete_directory_not_allowed_chars() {
     let boxed_delete = DirectoryExtImpl::delete_directory(path);
     assert!(boxed_delete.is_err());
 
-    let expected_error = format!("Path contains not allowed characters: whitespace, single quote, quotation mark, ampersand, pipe, semicolon. Path: {}", path);
-    assert_eq!(expected_error, boxed_delete.err().unwrap())
+    let error = boxed_delete.err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+    assert_eq!(path, error.path());
+
+    let expected_reason = format!("Path is not allowed: path contains character ';' which has special meaning in shell. Path: {}", path);
+    assert_eq!(expected_reason, error.message())
 }
 
 #[test]
@@ -113,7 +130,401 @@ fn create_directory_not_allowed_chars() {
     let boxed_delete = DirectoryExtImpl::create_directory(path);
     assert!(boxed_delete.is_err());
 
-    let expected_error = format!("Path contains not allowed characters: whitespace, single quote, quotation mark, ampersand, pipe, semicolon. Path: {}", path);
-    assert_eq!(expected_error, boxed_delete.err().unwrap())
+    let error = boxed_delete.err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+    assert_eq!(path, error.path());
+
+    let expected_reason = format!("Path is not allowed: path contains character ';' which has special meaning in shell. Path: {}", path);
+    assert_eq!(expected_reason, error.message())
+}
+
+
+fn create_copy_tree(root: &str) {
+    if DirectoryExtImpl::does_directory_exist(root) {
+        DirectoryExtImpl::delete_directory(root).unwrap();
+    }
+
+    let deeper = PathExtImpl::build_path(&[root, "sub", "deeper"]);
+    DirectoryExtImpl::create_directory(deeper.as_str()).unwrap();
+
+    let file_list = [
+        (PathExtImpl::build_path(&[root, "a.txt"]), "aaaaaaaaaa"),
+        (PathExtImpl::build_path(&[root, "sub", "b.txt"]), "bbbbbbbbbbbbbbbbbbbb"),
+        (PathExtImpl::build_path(&[root, "sub", "empty.txt"]), ""),
+        (PathExtImpl::build_path(&[root, "sub", "deeper", "c.txt"]), "cccccc"),
+    ];
+    for (path, content) in file_list {
+        FileExtImpl::write_file_with_mode(path.as_str(), content.as_bytes(), WriteMode::CreateNew).unwrap();
+    }
+}
+
+#[test]
+fn copy_directory() {
+    let from = "copy_directory_source";
+    let to = "copy_directory_destination";
+    create_copy_tree(from);
+    if DirectoryExtImpl::does_directory_exist(to) {
+        DirectoryExtImpl::delete_directory(to).unwrap();
+    }
+
+    let mut progress_list = vec![];
+    let options = CopyDirectoryOptions { block_size: Some(4), ..CopyDirectoryOptions::default() };
+    let status = DirectoryExtImpl::copy_directory(from, to, &options, |start, end, total| progress_list.push((start, end, total)), |_, _, _| false).unwrap();
+    assert_eq!(CopyDirectoryStatus::Completed { bytes_copied: 36 }, status);
+
+    assert_eq!("aaaaaaaaaa".as_bytes(), FileExtImpl::read_file(PathExtImpl::build_path(&[to, "a.txt"])).unwrap());
+    assert_eq!("bbbbbbbbbbbbbbbbbbbb".as_bytes(), FileExtImpl::read_file(PathExtImpl::build_path(&[to, "sub", "b.txt"])).unwrap());
+    assert!(FileExtImpl::read_file(PathExtImpl::build_path(&[to, "sub", "empty.txt"])).unwrap().is_empty());
+    assert_eq!("cccccc".as_bytes(), FileExtImpl::read_file(PathExtImpl::build_path(&[to, "sub", "deeper", "c.txt"])).unwrap());
+
+    let total = 36;
+    assert!(progress_list.iter().all(|(_, _, progress_total)| *progress_total == total));
+    assert_eq!(0, progress_list.first().unwrap().0);
+    assert_eq!(total - 1, progress_list.last().unwrap().1);
+    for window in progress_list.windows(2) {
+        assert_eq!(window[0].1 + 1, window[1].0);
+    }
+
+    let boxed_copy = DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::AlreadyExists, boxed_copy.err().unwrap().kind());
+
+    let options = CopyDirectoryOptions { overwrite: true, ..CopyDirectoryOptions::default() };
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!("aaaaaaaaaa".as_bytes(), FileExtImpl::read_file(PathExtImpl::build_path(&[to, "a.txt"])).unwrap());
+
+    DirectoryExtImpl::delete_directory(from).unwrap();
+    DirectoryExtImpl::delete_directory(to).unwrap();
+}
+
+#[test]
+fn copy_directory_cancel() {
+    let from = "copy_directory_cancel_source";
+    let to = "copy_directory_cancel_destination";
+    create_copy_tree(from);
+    if DirectoryExtImpl::does_directory_exist(to) {
+        DirectoryExtImpl::delete_directory(to).unwrap();
+    }
+
+    let options = CopyDirectoryOptions { block_size: Some(5), ..CopyDirectoryOptions::default() };
+    let status = DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, end, _| end >= 4).unwrap();
+    assert_eq!(CopyDirectoryStatus::Cancelled { bytes_copied: 5 }, status);
+
+    assert_eq!("aaaaa".as_bytes(), FileExtImpl::read_file(PathExtImpl::build_path(&[to, "a.txt"])).unwrap());
+    assert!(!FileExtImpl::does_file_exist(PathExtImpl::build_path(&[to, "sub", "b.txt"])));
+
+    DirectoryExtImpl::delete_directory(from).unwrap();
+    DirectoryExtImpl::delete_directory(to).unwrap();
+}
+
+#[test]
+fn copy_directory_nonexistent() {
+    let options = CopyDirectoryOptions::default();
+    let boxed_copy = DirectoryExtImpl::copy_directory("nonexistent_directory", "copy_directory_nonexistent", &options, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::NotFound, boxed_copy.err().unwrap().kind());
+    assert!(!DirectoryExtImpl::does_directory_exist("copy_directory_nonexistent"));
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn copy_directory_symlink_policy() {
+    let from = "copy_directory_symlink_source";
+    create_copy_tree(from);
+    std::os::unix::fs::symlink("a.txt", PathExtImpl::build_path(&[from, "link"])).unwrap();
+    std::os::unix::fs::symlink("deeper", PathExtImpl::build_path(&[from, "sub", "deeper-link"])).unwrap();
+
+    let to = "copy_directory_symlink_copy_link";
+    let options = CopyDirectoryOptions { symlink_policy: SymlinkPolicy::CopyLink, ..CopyDirectoryOptions::default() };
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+    let link = PathExtImpl::build_path(&[to, "link"]);
+    assert!(Path::new(&link).is_symlink());
+    assert_eq!("a.txt", std::fs::read_link(&link).unwrap().to_string_lossy());
+    assert_eq!("aaaaaaaaaa".as_bytes(), FileExtImpl::read_file(&link).unwrap());
+    DirectoryExtImpl::delete_directory(to).unwrap();
+
+    let to = "copy_directory_symlink_follow";
+    let options = CopyDirectoryOptions { symlink_policy: SymlinkPolicy::Follow, ..CopyDirectoryOptions::default() };
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+    let link = PathExtImpl::build_path(&[to, "link"]);
+    assert!(!Path::new(&link).is_symlink());
+    assert_eq!("aaaaaaaaaa".as_bytes(), FileExtImpl::read_file(&link).unwrap());
+    let followed_directory = PathExtImpl::build_path(&[to, "sub", "deeper-link", "c.txt"]);
+    assert_eq!("cccccc".as_bytes(), FileExtImpl::read_file(&followed_directory).unwrap());
+    DirectoryExtImpl::delete_directory(to).unwrap();
+
+    let to = "copy_directory_symlink_skip";
+    let options = CopyDirectoryOptions { symlink_policy: SymlinkPolicy::Skip, ..CopyDirectoryOptions::default() };
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert!(std::fs::symlink_metadata(PathExtImpl::build_path(&[to, "link"])).is_err());
+    assert!(FileExtImpl::does_file_exist(PathExtImpl::build_path(&[to, "a.txt"])));
+    DirectoryExtImpl::delete_directory(to).unwrap();
+
+    DirectoryExtImpl::delete_directory(from).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn copy_directory_symlink_loop() {
+    let from = "copy_directory_loop_source";
+    let to = "copy_directory_loop_destination";
+    create_copy_tree(from);
+    std::os::unix::fs::symlink("..", PathExtImpl::build_path(&[from, "sub", "loop"])).unwrap();
+
+    let options = CopyDirectoryOptions { symlink_policy: SymlinkPolicy::Follow, ..CopyDirectoryOptions::default() };
+    let boxed_copy = DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::InvalidData, boxed_copy.err().unwrap().kind());
+    assert!(!DirectoryExtImpl::does_directory_exist(to));
+
+    DirectoryExtImpl::delete_directory(from).unwrap();
+}
+
+#[test]
+fn delete_directory_with_options() {
+    let path = "delete_directory_with_options_tree";
+    create_copy_tree(path);
+
+    let options = DeleteDirectoryOptions { continue_on_error: true, ..DeleteDirectoryOptions::default() };
+    let report = DirectoryExtImpl::delete_directory_with_options(path, &options).unwrap();
+
+    assert!(report.is_complete());
+    // 4 files and 3 directories
+    assert_eq!(7, report.deleted_count);
+    assert_eq!(None, report.staged_path);
+    assert!(!DirectoryExtImpl::does_directory_exist(path));
 }
 
+#[test]
+fn delete_directory_with_staging() {
+    let path = "delete_directory_staging_tree";
+    let staging = "delete_directory_staging";
+    create_copy_tree(path);
+    if !DirectoryExtImpl::does_directory_exist(staging) {
+        DirectoryExtImpl::create_directory(staging).unwrap();
+    }
+
+    let options = DeleteDirectoryOptions { staging_directory: Some(staging.to_string()), ..DeleteDirectoryOptions::default() };
+    let report = DirectoryExtImpl::delete_directory_with_options(path, &options).unwrap();
+
+    assert!(report.is_complete());
+    let staged_path = report.staged_path.unwrap();
+    assert!(staged_path.starts_with(staging));
+    assert!(!DirectoryExtImpl::does_directory_exist(path));
+    assert!(!DirectoryExtImpl::does_directory_exist(&staged_path));
+    assert_eq!(0, std::fs::read_dir(staging).unwrap().count());
+
+    DirectoryExtImpl::delete_directory(staging).unwrap();
+}
+
+#[test]
+fn delete_directory_staging_validation() {
+    let path = "delete_directory_staging_validation_tree";
+    create_copy_tree(path);
+
+    let inside = PathExtImpl::build_path(&[path, "sub"]);
+    for staging in [path.to_string(), inside] {
+        let options = DeleteDirectoryOptions { staging_directory: Some(staging.clone()), ..DeleteDirectoryOptions::default() };
+        let error = DirectoryExtImpl::delete_directory_with_options(path, &options).err().unwrap();
+        assert_eq!(ErrorKind::InvalidInput, error.kind());
+        assert_eq!(staging, error.path());
+    }
+
+    let options = DeleteDirectoryOptions { staging_directory: Some("staging;rm".to_string()), ..DeleteDirectoryOptions::default() };
+    let error = DirectoryExtImpl::delete_directory_with_options(path, &options).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+
+    let options = DeleteDirectoryOptions { staging_directory: Some("delete_directory_missing_staging".to_string()), ..DeleteDirectoryOptions::default() };
+    let error = DirectoryExtImpl::delete_directory_with_options(path, &options).err().unwrap();
+    assert_eq!(ErrorKind::NotFound, error.kind());
+
+    assert!(DirectoryExtImpl::does_directory_exist(path));
+    DirectoryExtImpl::delete_directory(path).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn delete_directory_does_not_follow_symlinks() {
+    let path = "delete_directory_symlink_tree";
+    let outside = "delete_directory_symlink_outside";
+    create_copy_tree(path);
+    create_copy_tree(outside);
+
+    let outside_absolute = PathExtImpl::build_path(&[&PathExtImpl::working_directory().unwrap(), outside]);
+    std::os::unix::fs::symlink(&outside_absolute, PathExtImpl::build_path(&[path, "outside-directory"])).unwrap();
+    std::os::unix::fs::symlink(PathExtImpl::build_path(&[&outside_absolute, "a.txt"]), PathExtImpl::build_path(&[path, "outside-file"])).unwrap();
+
+    // symlink to a directory is removed itself, the target stays intact
+    let link = PathExtImpl::build_path(&[path, "outside-directory"]);
+    let report = DirectoryExtImpl::delete_directory_with_options(&link, &DeleteDirectoryOptions::default()).unwrap();
+    assert_eq!(1, report.deleted_count);
+    assert!(std::fs::symlink_metadata(&link).is_err());
+    assert!(FileExtImpl::does_file_exist(PathExtImpl::build_path(&[outside, "a.txt"])));
+
+    // symlink to a file is not a directory
+    let boxed_delete = DirectoryExtImpl::delete_directory("delete_directory_symlink_tree/outside-file");
+    assert_eq!(ErrorKind::NotFound, boxed_delete.err().unwrap().kind());
+
+    DirectoryExtImpl::delete_directory(path).unwrap();
+    assert!(!DirectoryExtImpl::does_directory_exist(path));
+    assert_eq!("aaaaaaaaaa".as_bytes(), FileExtImpl::read_file(PathExtImpl::build_path(&[outside, "a.txt"])).unwrap());
+    assert!(FileExtImpl::does_file_exist(PathExtImpl::build_path(&[outside, "sub", "deeper", "c.txt"])));
+
+    DirectoryExtImpl::delete_directory(outside).unwrap();
+}
+
+#[test]
+fn delete_directory_nonexistent() {
+    let boxed_delete = DirectoryExtImpl::delete_directory_with_options("delete_directory_nonexistent", &DeleteDirectoryOptions::default());
+    let error = boxed_delete.err().unwrap();
+    assert_eq!(ErrorKind::NotFound, error.kind());
+    assert_eq!(Operation::DeleteDirectory, error.operation());
+}
+
+#[test]
+fn copy_directory_ignore_rules() {
+    let from = "copy_directory_ignore_source";
+    let to = "copy_directory_ignore_destination";
+    create_copy_tree(from);
+    FileExtImpl::write_file_with_mode(PathExtImpl::build_path(&[from, "sub", ".gitignore"]), "deeper/\nempty.txt\n".as_bytes(), WriteMode::CreateNew).unwrap();
+    if DirectoryExtImpl::does_directory_exist(to) {
+        DirectoryExtImpl::delete_directory(to).unwrap();
+    }
+
+    let mut ignore_rules = IgnoreRules::parse("a.txt\n").unwrap();
+    ignore_rules.add_ignore_file_name(".gitignore");
+    let options = CopyDirectoryOptions { ignore_rules, ..CopyDirectoryOptions::default() };
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+
+    assert!(!FileExtImpl::does_file_exist(PathExtImpl::build_path(&[to, "a.txt"])));
+    assert!(FileExtImpl::does_file_exist(PathExtImpl::build_path(&[to, "sub", "b.txt"])));
+    assert!(FileExtImpl::does_file_exist(PathExtImpl::build_path(&[to, "sub", ".gitignore"])));
+    assert!(!FileExtImpl::does_file_exist(PathExtImpl::build_path(&[to, "sub", "empty.txt"])));
+    assert!(!DirectoryExtImpl::does_directory_exist(PathExtImpl::build_path(&[to, "sub", "deeper"])));
+
+    DirectoryExtImpl::delete_directory(from).unwrap();
+    DirectoryExtImpl::delete_directory(to).unwrap();
+}
+
+#[test]
+fn delete_directory_ignore_rules() {
+    let path = "delete_directory_ignore_tree";
+    create_copy_tree(path);
+
+    let options = DeleteDirectoryOptions { ignore_rules: IgnoreRules::parse("c.txt\n").unwrap(), ..DeleteDirectoryOptions::default() };
+    let report = DirectoryExtImpl::delete_directory_with_options(path, &options).unwrap();
+
+    assert!(report.is_complete());
+    assert_eq!(1, report.ignored_count);
+    // a.txt, b.txt, empty.txt
+    assert_eq!(3, report.deleted_count);
+    assert!(FileExtImpl::does_file_exist(PathExtImpl::build_path(&[path, "sub", "deeper", "c.txt"])));
+    assert!(!FileExtImpl::does_file_exist(PathExtImpl::build_path(&[path, "a.txt"])));
+
+    let options = DeleteDirectoryOptions { staging_directory: Some("test".to_string()), ..options };
+    let boxed_delete = DirectoryExtImpl::delete_directory_with_options(path, &options);
+    assert_eq!(ErrorKind::InvalidInput, boxed_delete.err().unwrap().kind());
+
+    DirectoryExtImpl::delete_directory(path).unwrap();
+}
+
+#[test]
+fn create_directory_existing_parent() {
+    let parent = "create_directory_existing_parent";
+    if DirectoryExtImpl::does_directory_exist(parent) {
+        DirectoryExtImpl::delete_directory(parent).unwrap();
+    }
+    DirectoryExtImpl::create_directory(parent).unwrap();
+
+    let path = PathExtImpl::build_path(&[parent, "sub", "subsub"]);
+    DirectoryExtImpl::create_directory(&path).unwrap();
+    assert!(DirectoryExtImpl::does_directory_exist(&path));
+
+    let boxed_create = DirectoryExtImpl::create_directory(&path);
+    assert_eq!(ErrorKind::AlreadyExists, boxed_create.err().unwrap().kind());
+
+    DirectoryExtImpl::delete_directory(parent).unwrap();
+}
+
+#[test]
+fn ensure_directory() {
+    let parent = "ensure_directory_tree";
+    if DirectoryExtImpl::does_directory_exist(parent) {
+        DirectoryExtImpl::delete_directory(parent).unwrap();
+    }
+    DirectoryExtImpl::create_directory(parent).unwrap();
+
+    let first = PathExtImpl::build_path(&[parent, "a"]);
+    let second = PathExtImpl::build_path(&[parent, "a", "b"]);
+    let created_list = DirectoryExtImpl::ensure_directory(&second, None).unwrap();
+    assert_eq!(vec![first.clone(), second.clone()], created_list);
+
+    let created_list = DirectoryExtImpl::ensure_directory(&second, None).unwrap();
+    assert!(created_list.is_empty());
+
+    // file in the middle of the path
+    let file = PathExtImpl::build_path(&[parent, "file"]);
+    FileExtImpl::create_file(&file).unwrap();
+    let boxed_ensure = DirectoryExtImpl::ensure_directory(&PathExtImpl::build_path(&[parent, "file", "c"]), None);
+    let error = boxed_ensure.err().unwrap();
+    assert_eq!(Operation::CreateDirectory, error.operation());
+
+    // nothing is left behind if creation fails, last component is longer than file systems allow
+    let too_long_name = "x".repeat(300);
+    let boxed_ensure = DirectoryExtImpl::ensure_directory(&PathExtImpl::build_path(&[parent, "new", "newer", &too_long_name]), None);
+    assert!(boxed_ensure.is_err());
+    assert!(!DirectoryExtImpl::does_directory_exist(PathExtImpl::build_path(&[parent, "new"])));
+
+    DirectoryExtImpl::delete_directory(parent).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn ensure_directory_absolute_path_and_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let parent = "ensure_directory_mode_tree";
+    if DirectoryExtImpl::does_directory_exist(parent) {
+        DirectoryExtImpl::delete_directory(parent).unwrap();
+    }
+
+    let working_directory = PathExtImpl::working_directory().unwrap();
+    let path = PathExtImpl::build_path(&[&working_directory, parent, "a", "b"]);
+    let created_list = DirectoryExtImpl::ensure_directory(&path, Some(0o700)).unwrap();
+    assert_eq!(3, created_list.len());
+    for created in created_list {
+        let mode = std::fs::metadata(&created).unwrap().permissions().mode() & 0o777;
+        assert_eq!(0o700, mode);
+    }
+
+    DirectoryExtImpl::delete_directory(parent).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn copy_directory_non_utf8_file_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let from = "copy_directory_non_utf8_source";
+    let to = "copy_directory_non_utf8_destination";
+    create_copy_tree(from);
+    if DirectoryExtImpl::does_directory_exist(to) {
+        DirectoryExtImpl::delete_directory(to).unwrap();
+    }
+
+    let file_name = OsStr::from_bytes(b"caf\xe9.txt");
+    std::fs::write(Path::new(from).join("sub").join(file_name), b"latin-1").unwrap();
+
+    let options = CopyDirectoryOptions::default();
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+    let copied = Path::new(to).join("sub").join(file_name);
+    assert_eq!(b"latin-1".to_vec(), std::fs::read(&copied).unwrap());
+
+    // overwrite replaces the very same file instead of a lossy spelling of it
+    std::fs::write(&copied, b"changed").unwrap();
+    let options = CopyDirectoryOptions { overwrite: true, ..CopyDirectoryOptions::default() };
+    DirectoryExtImpl::copy_directory(from, to, &options, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(b"latin-1".to_vec(), std::fs::read(&copied).unwrap());
+    assert_eq!(4, std::fs::read_dir(Path::new(to).join("sub")).unwrap().count());
+
+    DirectoryExtImpl::delete_directory(from).unwrap();
+    DirectoryExtImpl::delete_directory(to).unwrap();
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use crate::directory_entry::{DirectoryEntry, EntryKind, ListDirectoryOptions};
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::ignore_rules::IgnoreRules;
+
+#[cfg(test)]
+mod tests;
+
+/// Recursively walks the directory tree, depth first. Each directory is listed
+/// right before its content, entries of every directory are sorted according to the options.
+///
+/// Directory is read only when the walker reaches it. If a subdirectory can not be read,
+/// or a followed symlink leads back to one of its ancestors, the directory entry itself is returned,
+/// followed by an error, and walking continues with the next entry.
+pub struct DirectoryWalker {
+    root: String,
+    options: ListDirectoryOptions,
+    // rules from the options, extended with ignore files of the directories walked so far
+    ignore_rules: IgnoreRules,
+    // entries of each directory on the way down, in reversed order, so next entry is popped from the end
+    frame_list: Vec<Vec<DirectoryEntry>>,
+    // canonical paths of the directories in frame_list, used to detect symlink loops
+    ancestor_list: Vec<PathBuf>,
+    pending_error: Option<FileExtError>,
+}
+
+impl DirectoryWalker {
+    pub(crate) fn new(path: &Path, options: ListDirectoryOptions) -> Result<DirectoryWalker, FileExtError> {
+        let boxed_canonical = fs::canonicalize(path);
+        if boxed_canonical.is_err() {
+            let error = boxed_canonical.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ListDirectory, &path.to_string_lossy(), error))
+        }
+
+        let mut ignore_rules = options.ignore_rules.clone();
+        let boxed_load = ignore_rules.load_ignore_files(path, "");
+        if boxed_load.is_err() {
+            let error = boxed_load.err().unwrap();
+            return Err(error)
+        }
+
+        let boxed_entry_list = DirectoryExtImpl::read_entries(path, 1, &options);
+        if boxed_entry_list.is_err() {
+            let error = boxed_entry_list.err().unwrap();
+            return Err(error)
+        }
+
+        let mut entry_list = boxed_entry_list.unwrap();
+        entry_list.reverse();
+
+        Ok(DirectoryWalker {
+            root: path.to_string_lossy().to_string(),
+            options,
+            ignore_rules,
+            frame_list: vec![entry_list],
+            ancestor_list: vec![boxed_canonical.unwrap()],
+            pending_error: None,
+        })
+    }
+
+    fn should_descend(&self, entry: &DirectoryEntry) -> bool {
+        if entry.kind != EntryKind::Directory {
+            return false
+        }
+
+        match self.options.max_depth {
+            Some(max_depth) => entry.depth < max_depth,
+            None => true,
+        }
+    }
+
+    fn relative_path<'a>(&self, entry: &'a DirectoryEntry) -> &'a str {
+        let relative_path = entry.path.strip_prefix(self.root.as_str()).unwrap_or(&entry.path);
+        relative_path.trim_start_matches(['/', '\\'])
+    }
+
+    fn descend(&mut self, entry: &DirectoryEntry) -> Result<(), FileExtError> {
+        // os_path is used to touch the file system, so directories with names which are not valid UTF-8 are walked too
+        let boxed_canonical = fs::canonicalize(&entry.os_path);
+        if boxed_canonical.is_err() {
+            let error = boxed_canonical.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ListDirectory, &entry.path, error))
+        }
+
+        let canonical = boxed_canonical.unwrap();
+        if self.ancestor_list.contains(&canonical) {
+            let message = format!("symlink loop detected at {}", entry.path);
+            return Err(FileExtError::from_kind(Operation::ListDirectory, &entry.path, ErrorKind::InvalidData, message))
+        }
+
+        let relative_path = self.relative_path(entry).to_string();
+        let boxed_load = self.ignore_rules.load_ignore_files(&entry.os_path, &relative_path);
+        if boxed_load.is_err() {
+            let error = boxed_load.err().unwrap();
+            return Err(error)
+        }
+
+        let boxed_entry_list = DirectoryExtImpl::read_entries(&entry.os_path, entry.depth + 1, &self.options);
+        if boxed_entry_list.is_err() {
+            let error = boxed_entry_list.err().unwrap();
+            return Err(error)
+        }
+
+        let mut entry_list = boxed_entry_list.unwrap();
+        entry_list.reverse();
+        self.frame_list.push(entry_list);
+        self.ancestor_list.push(canonical);
+        Ok(())
+    }
+}
+
+impl Iterator for DirectoryWalker {
+    type Item = Result<DirectoryEntry, FileExtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.pending_error.take() {
+            return Some(Err(error))
+        }
+
+        loop {
+            let frame = self.frame_list.last_mut()?;
+            let boxed_entry = frame.pop();
+            if boxed_entry.is_none() {
+                self.frame_list.pop();
+                self.ancestor_list.pop();
+                continue;
+            }
+
+            let entry = boxed_entry.unwrap();
+            if self.ignore_rules.is_ignored(self.relative_path(&entry), entry.kind == EntryKind::Directory) {
+                continue;
+            }
+
+            if self.should_descend(&entry) {
+                let boxed_descend = self.descend(&entry);
+                if boxed_descend.is_err() {
+                    self.pending_error = boxed_descend.err();
+                }
+            }
+            return Some(Ok(entry))
+        }
+    }
+}
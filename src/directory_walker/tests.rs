@@ -0,0 +1,145 @@
+use std::io::ErrorKind;
+use crate::directory_entry::{EntryKind, ListDirectoryOptions, SortBy};
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_impl::FileExtImpl;
+use crate::path_ext_impl::PathExtImpl;
+use crate::write_mode::WriteMode;
+
+fn create_tree(root: &str) {
+    if DirectoryExtImpl::does_directory_exist(root) {
+        DirectoryExtImpl::delete_directory(root).unwrap();
+    }
+
+    DirectoryExtImpl::create_directory(PathExtImpl::build_path(&[root, "b", "c"])).unwrap();
+    std::fs::create_dir(PathExtImpl::build_path(&[root, "a"])).unwrap();
+    FileExtImpl::write_file_with_mode(PathExtImpl::build_path(&[root, "z.txt"]), "zzz".as_bytes(), WriteMode::CreateNew).unwrap();
+    FileExtImpl::write_file_with_mode(PathExtImpl::build_path(&[root, "b", "y.txt"]), "yy".as_bytes(), WriteMode::CreateNew).unwrap();
+    FileExtImpl::write_file_with_mode(PathExtImpl::build_path(&[root, "b", "c", "x.txt"]), "x".as_bytes(), WriteMode::CreateNew).unwrap();
+}
+
+fn relative_path_list(root: &str, options: &ListDirectoryOptions) -> Vec<(String, usize)> {
+    let prefix = [root, PathExtImpl::get_path_separator().as_str()].join("");
+    DirectoryExtImpl::walk_directory(root, options).unwrap()
+        .map(|boxed_entry| {
+            let entry = boxed_entry.unwrap();
+            let relative_path = entry.path.strip_prefix(&prefix).unwrap().replace('\\', "/");
+            (relative_path, entry.depth)
+        })
+        .collect()
+}
+
+#[test]
+fn walk_directory() {
+    let root = "walk_directory_tree";
+    create_tree(root);
+
+    let path_list = relative_path_list(root, &ListDirectoryOptions::default());
+    let expected = vec![
+        ("a".to_string(), 1),
+        ("b".to_string(), 1),
+        ("b/c".to_string(), 2),
+        ("b/c/x.txt".to_string(), 3),
+        ("b/y.txt".to_string(), 2),
+        ("z.txt".to_string(), 1),
+    ];
+    assert_eq!(expected, path_list);
+
+    let options = ListDirectoryOptions { max_depth: Some(2), sort_by: SortBy::Kind, descending: false, follow_symlinks: false, ..ListDirectoryOptions::default() };
+    let path_list = relative_path_list(root, &options);
+    let expected = vec![
+        ("a".to_string(), 1),
+        ("b".to_string(), 1),
+        ("b/c".to_string(), 2),
+        ("b/y.txt".to_string(), 2),
+        ("z.txt".to_string(), 1),
+    ];
+    assert_eq!(expected, path_list);
+
+    let options = ListDirectoryOptions { max_depth: Some(1), ..ListDirectoryOptions::default() };
+    assert_eq!(3, relative_path_list(root, &options).len());
+
+    DirectoryExtImpl::delete_directory(root).unwrap();
+}
+
+#[test]
+fn list_directory() {
+    let root = "list_directory_tree";
+    create_tree(root);
+
+    let options = ListDirectoryOptions { sort_by: SortBy::Size, descending: true, ..ListDirectoryOptions::default() };
+    let entry_list = DirectoryExtImpl::list_directory(root, &options).unwrap();
+    assert_eq!(3, entry_list.len());
+
+    let file = entry_list.iter().find(|entry| entry.name == "z.txt").unwrap();
+    assert_eq!(EntryKind::File, file.kind);
+    assert_eq!(3, file.size);
+    assert!(file.modified > 0);
+    assert!(!file.readonly);
+    assert!(!file.is_symlink);
+    #[cfg(target_family = "unix")]
+    assert!(file.mode.is_some());
+
+    let directory = entry_list.iter().find(|entry| entry.name == "b").unwrap();
+    assert_eq!(EntryKind::Directory, directory.kind);
+    assert_eq!(PathExtImpl::build_path(&[root, "b"]), directory.path);
+
+    let boxed_list = DirectoryExtImpl::list_directory("list_directory_nonexistent", &options);
+    assert_eq!(ErrorKind::NotFound, boxed_list.err().unwrap().kind());
+
+    DirectoryExtImpl::delete_directory(root).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn walk_directory_symlinks() {
+    let root = "walk_directory_symlink_tree";
+    create_tree(root);
+    std::os::unix::fs::symlink("..", PathExtImpl::build_path(&[root, "b", "loop"])).unwrap();
+    std::os::unix::fs::symlink("c", PathExtImpl::build_path(&[root, "b", "d"])).unwrap();
+
+    let path_list = relative_path_list(root, &ListDirectoryOptions::default());
+    assert!(path_list.contains(&("b/loop".to_string(), 2)));
+    assert!(!path_list.iter().any(|(path, _)| path.starts_with("b/d/")));
+    let entry_list = DirectoryExtImpl::list_directory(PathExtImpl::build_path(&[root, "b"]), &ListDirectoryOptions::default()).unwrap();
+    let symlink = entry_list.iter().find(|entry| entry.name == "loop").unwrap();
+    assert_eq!(EntryKind::Symlink, symlink.kind);
+    assert!(symlink.is_symlink);
+
+    let options = ListDirectoryOptions { follow_symlinks: true, ..ListDirectoryOptions::default() };
+    let mut loop_error_count = 0;
+    let mut entry_path_list = vec![];
+    for boxed_entry in DirectoryExtImpl::walk_directory(root, &options).unwrap() {
+        match boxed_entry {
+            Ok(entry) => entry_path_list.push(entry.path),
+            Err(error) => {
+                assert_eq!(ErrorKind::InvalidData, error.kind());
+                loop_error_count += 1;
+            }
+        }
+    }
+    assert_eq!(1, loop_error_count);
+    assert!(entry_path_list.contains(&PathExtImpl::build_path(&[root, "b", "d", "x.txt"])));
+
+    DirectoryExtImpl::delete_directory(root).unwrap();
+}
+
+#[test]
+fn walk_directory_ignore_rules() {
+    let root = "walk_directory_ignore_tree";
+    create_tree(root);
+    FileExtImpl::write_file_with_mode(PathExtImpl::build_path(&[root, ".ignore"]), "a/\n*.txt\n".as_bytes(), WriteMode::CreateNew).unwrap();
+    FileExtImpl::write_file_with_mode(PathExtImpl::build_path(&[root, "b", ".ignore"]), "!y.txt\nc\n".as_bytes(), WriteMode::CreateNew).unwrap();
+
+    let mut options = ListDirectoryOptions::default();
+    options.ignore_rules.add_ignore_file_name(".ignore");
+    options.ignore_rules.add_rule(".ignore").unwrap();
+
+    let path_list : Vec<String> = relative_path_list(root, &options).into_iter().map(|(path, _)| path).collect();
+    assert_eq!(vec!["b".to_string(), "b/y.txt".to_string()], path_list);
+
+    let entry_list = DirectoryExtImpl::list_directory(root, &options).unwrap();
+    let name_list : Vec<&str> = entry_list.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(vec!["b"], name_list);
+
+    DirectoryExtImpl::delete_directory(root).unwrap();
+}
@@ -0,0 +1,221 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[cfg(test)]
+mod tests;
+
+/// Operation that was performed when the error occurred
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Operation {
+    Open,
+    Read,
+    Seek,
+    Write,
+    Create,
+    Delete,
+    Rename,
+    Metadata,
+    Copy,
+    CopyDirectory,
+    CreateDirectory,
+    DeleteDirectory,
+    ListDirectory,
+    Glob,
+    ResolvePath,
+    CreateSymlink,
+    CreateHardLink,
+    ReadSymlink,
+    ResolveSymlink,
+    WorkingDirectory,
+    TempFolder,
+    CurrentUser,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Operation::Open => "open file",
+            Operation::Read => "read file",
+            Operation::Seek => "seek file",
+            Operation::Write => "write to file",
+            Operation::Create => "create file",
+            Operation::Delete => "delete file",
+            Operation::Rename => "rename file",
+            Operation::Metadata => "read metadata",
+            Operation::Copy => "copy file",
+            Operation::CopyDirectory => "copy directory",
+            Operation::CreateDirectory => "create directory",
+            Operation::DeleteDirectory => "delete directory",
+            Operation::ListDirectory => "list directory",
+            Operation::Glob => "parse glob pattern",
+            Operation::ResolvePath => "resolve path",
+            Operation::CreateSymlink => "create symlink",
+            Operation::CreateHardLink => "create hard link",
+            Operation::ReadSymlink => "read symlink",
+            Operation::ResolveSymlink => "resolve symlink",
+            Operation::WorkingDirectory => "get working directory",
+            Operation::TempFolder => "get temp folder",
+            Operation::CurrentUser => "get current user",
+        };
+        f.write_str(description)
+    }
+}
+
+/// Reason untrusted path was rejected by `resolve_within`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PathRejection {
+    /// Path has a drive or UNC prefix, so it can not be relative to the root
+    AbsolutePath,
+    /// `..` segments lead outside of the root
+    Traversal,
+    /// Path is inside the root, but symlinks on the way point outside of it
+    SymlinkEscape,
+    /// Path contains NUL character
+    InvalidCharacter,
+}
+
+impl Display for PathRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            PathRejection::AbsolutePath => "path is absolute",
+            PathRejection::Traversal => "path leads outside of the root directory",
+            PathRejection::SymlinkEscape => "symlink leads outside of the root directory",
+            PathRejection::InvalidCharacter => "path contains NUL character",
+        };
+        f.write_str(description)
+    }
+}
+
+/// Error returned by every `FileExt` function.
+///
+/// Each variant carries the operation that failed, the path it was performed on
+/// (empty if the operation is not tied to a path) and a human readable message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileExtError {
+    /// Path was rejected before touching the filesystem
+    InvalidPath { operation: Operation, path: String, reason: String },
+    NotFound { operation: Operation, path: String, message: String },
+    AlreadyExists { operation: Operation, path: String, message: String },
+    PermissionDenied { operation: Operation, path: String, message: String },
+    /// Arguments are not valid for the given path, for example a byte range outside of the file
+    InvalidInput { operation: Operation, path: String, message: String },
+    /// Any other failure reported by the operating system
+    Io { operation: Operation, path: String, kind: io::ErrorKind, message: String },
+    /// Untrusted path does not stay within the root directory
+    OutsideRoot { operation: Operation, path: String, rejection: PathRejection, message: String },
+}
+
+impl FileExtError {
+    pub(crate) fn invalid_path(operation: Operation, path: &str, reason: String) -> FileExtError {
+        FileExtError::InvalidPath { operation, path: path.to_string(), reason }
+    }
+
+    pub(crate) fn outside_root(path: &str, rejection: PathRejection) -> FileExtError {
+        FileExtError::OutsideRoot { operation: Operation::ResolvePath, path: path.to_string(), rejection, message: rejection.to_string() }
+    }
+
+    pub(crate) fn from_io(operation: Operation, path: &str, error: io::Error) -> FileExtError {
+        FileExtError::from_kind(operation, path, error.kind(), error.to_string())
+    }
+
+    pub(crate) fn from_kind(operation: Operation, path: &str, kind: io::ErrorKind, message: String) -> FileExtError {
+        let path = path.to_string();
+        match kind {
+            io::ErrorKind::NotFound => FileExtError::NotFound { operation, path, message },
+            io::ErrorKind::AlreadyExists => FileExtError::AlreadyExists { operation, path, message },
+            io::ErrorKind::PermissionDenied => FileExtError::PermissionDenied { operation, path, message },
+            io::ErrorKind::InvalidInput => FileExtError::InvalidInput { operation, path, message },
+            _ => FileExtError::Io { operation, path, kind, message },
+        }
+    }
+
+    pub fn operation(&self) -> Operation {
+        match self {
+            FileExtError::InvalidPath { operation, .. } => *operation,
+            FileExtError::NotFound { operation, .. } => *operation,
+            FileExtError::AlreadyExists { operation, .. } => *operation,
+            FileExtError::PermissionDenied { operation, .. } => *operation,
+            FileExtError::InvalidInput { operation, .. } => *operation,
+            FileExtError::Io { operation, .. } => *operation,
+            FileExtError::OutsideRoot { operation, .. } => *operation,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self {
+            FileExtError::InvalidPath { path, .. } => path,
+            FileExtError::NotFound { path, .. } => path,
+            FileExtError::AlreadyExists { path, .. } => path,
+            FileExtError::PermissionDenied { path, .. } => path,
+            FileExtError::InvalidInput { path, .. } => path,
+            FileExtError::Io { path, .. } => path,
+            FileExtError::OutsideRoot { path, .. } => path,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            FileExtError::InvalidPath { reason, .. } => reason,
+            FileExtError::NotFound { message, .. } => message,
+            FileExtError::AlreadyExists { message, .. } => message,
+            FileExtError::PermissionDenied { message, .. } => message,
+            FileExtError::InvalidInput { message, .. } => message,
+            FileExtError::Io { message, .. } => message,
+            FileExtError::OutsideRoot { message, .. } => message,
+        }
+    }
+
+    /// Returns `io::ErrorKind` of the originating error. Rejected paths are reported as `InvalidInput`.
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            FileExtError::InvalidPath { .. } => io::ErrorKind::InvalidInput,
+            FileExtError::NotFound { .. } => io::ErrorKind::NotFound,
+            FileExtError::AlreadyExists { .. } => io::ErrorKind::AlreadyExists,
+            FileExtError::PermissionDenied { .. } => io::ErrorKind::PermissionDenied,
+            FileExtError::InvalidInput { .. } => io::ErrorKind::InvalidInput,
+            FileExtError::Io { kind, .. } => *kind,
+            FileExtError::OutsideRoot { .. } => io::ErrorKind::InvalidInput,
+        }
+    }
+
+    /// Returns the reason untrusted path was rejected, None for other errors
+    pub fn rejection(&self) -> Option<PathRejection> {
+        match self {
+            FileExtError::OutsideRoot { rejection, .. } => Some(*rejection),
+            _ => None,
+        }
+    }
+
+    /// Renders error as html paragraphs, suitable to be sent as a body of http response
+    pub fn to_html(&self) -> String {
+        format!("<p>Unable to {}: {}</p> <p>error: {}</p>", self.operation(), escape_html(self.path()), escape_html(self.message()))
+    }
+}
+
+impl Display for FileExtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path().is_empty() {
+            return write!(f, "unable to {}: {}", self.operation(), self.message())
+        }
+        write!(f, "unable to {}: {}, error: {}", self.operation(), self.path(), self.message())
+    }
+}
+
+impl Error for FileExtError {}
+
+impl From<FileExtError> for io::Error {
+    fn from(error: FileExtError) -> Self {
+        io::Error::new(error.kind(), error.to_string())
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
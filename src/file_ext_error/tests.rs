@@ -0,0 +1,56 @@
+use std::io;
+use crate::file_ext_error::{FileExtError, Operation, PathRejection};
+
+#[test]
+fn from_io_maps_kind_to_variant() {
+    let error = io::Error::new(io::ErrorKind::NotFound, "No such file or directory");
+    let file_ext_error = FileExtError::from_io(Operation::Open, "test/missing", error);
+
+    assert!(matches!(file_ext_error, FileExtError::NotFound { .. }));
+    assert_eq!(io::ErrorKind::NotFound, file_ext_error.kind());
+    assert_eq!(Operation::Open, file_ext_error.operation());
+    assert_eq!("test/missing", file_ext_error.path());
+    assert_eq!("No such file or directory", file_ext_error.message());
+}
+
+#[test]
+fn from_io_keeps_unmapped_kind() {
+    let error = io::Error::new(io::ErrorKind::UnexpectedEof, "eof");
+    let file_ext_error = FileExtError::from_io(Operation::Read, "test/index.html", error);
+
+    assert!(matches!(file_ext_error, FileExtError::Io { .. }));
+    assert_eq!(io::ErrorKind::UnexpectedEof, file_ext_error.kind());
+}
+
+#[test]
+fn display_is_plain_text() {
+    let file_ext_error = FileExtError::from_kind(Operation::Open, "test/missing", io::ErrorKind::NotFound, "not found".to_string());
+    assert_eq!("unable to open file: test/missing, error: not found", file_ext_error.to_string());
+
+    let file_ext_error = FileExtError::from_kind(Operation::CurrentUser, "", io::ErrorKind::Other, "no user".to_string());
+    assert_eq!("unable to get current user: no user", file_ext_error.to_string());
+}
+
+#[test]
+fn html_is_opt_in_and_escaped() {
+    let file_ext_error = FileExtError::invalid_path(Operation::Read, "<script>", "not allowed".to_string());
+    assert_eq!(
+        "<p>Unable to read file: &lt;script&gt;</p> <p>error: not allowed</p>",
+        file_ext_error.to_html()
+    );
+    assert_eq!(io::ErrorKind::InvalidInput, file_ext_error.kind());
+}
+
+#[test]
+fn outside_root_keeps_rejection() {
+    let file_ext_error = FileExtError::outside_root("../etc/passwd", PathRejection::Traversal);
+
+    assert!(matches!(file_ext_error, FileExtError::OutsideRoot { .. }));
+    assert_eq!(Some(PathRejection::Traversal), file_ext_error.rejection());
+    assert_eq!(io::ErrorKind::InvalidInput, file_ext_error.kind());
+    assert_eq!(Operation::ResolvePath, file_ext_error.operation());
+    assert_eq!("unable to resolve path: ../etc/passwd, error: path leads outside of the root directory", file_ext_error.to_string());
+
+    let file_ext_error = FileExtError::from_kind(Operation::Open, "test/missing", io::ErrorKind::NotFound, "not found".to_string());
+    assert_eq!(None, file_ext_error.rejection());
+}
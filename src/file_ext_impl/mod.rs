@@ -2,55 +2,69 @@
 mod tests;
 
 use std::fs;
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Read, Seek, SeekFrom, Write};
-use std::path::Path;
+use std::fs::{File, Permissions};
+use std::io;
+use std::io::{BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use crate::byte_range::{ByteRange, ByteRangeContent};
+use crate::date_time_ext::DateTimeExt;
 use crate::FileExt;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::file_range::FileRange;
+use crate::file_reader::FileReader;
 use crate::filter_string::FilterString;
+use crate::path_ext_impl::PathExtImpl;
+use crate::symlink_chain::SymlinkChain;
+use crate::symlink_ext_impl::SymlinkExtImpl;
+use crate::temp_entry;
+use crate::temp_entry::TempFile;
+use crate::write_mode::WriteMode;
 
 pub struct FileExtImpl;
 
 impl FileExtImpl {
-    pub fn read_file(filepath: &str) -> Result<Vec<u8>, String> {
-        let boxed_check = FilterString::is_valid_input_string(filepath);
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::Read, &filepath, message))
         }
 
         let mut file_content = Vec::new();
-        let boxed_open = File::open(filepath);
+        let boxed_open = File::open(path);
         if boxed_open.is_err() {
-            let error_msg = boxed_open.err().unwrap();
-            let error = format!("<p>Unable to open file: {}</p> <p>error: {}</p>", filepath, error_msg);
-            return Err(error)
-        } else {
-            let mut file = boxed_open.unwrap();
-            let boxed_read= file.read_to_end(&mut file_content);
-            if boxed_read.is_err() {
-                let error_msg = boxed_read.err().unwrap();
-                let error = format!("<p>Unable to read file: {}</p> <p>error: {}</p>", filepath, error_msg);
-                return Err(error)
-            }
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &filepath, error))
+        }
+
+        let mut file = boxed_open.unwrap();
+        let boxed_read= file.read_to_end(&mut file_content);
+        if boxed_read.is_err() {
+            let error = boxed_read.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Read, &filepath, error))
         }
         Ok(file_content)
     }
 
-    pub fn read_file_partially(filepath: &str, start: u64, end: u64) -> Result<Vec<u8>, String> {
-        let boxed_check = FilterString::is_valid_input_string(filepath);
+    pub fn read_file_partially<P: AsRef<Path>>(path: P, start: u64, end: u64) -> Result<Vec<u8>, FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::Read, &filepath, message))
         }
 
         let mut file_content = Vec::new();
 
         let buff_length = (end - start) + 1;
-        let boxed_open = File::open(filepath);
+        let boxed_open = File::open(path);
         if boxed_open.is_err() {
-            let error_msg = boxed_open.err().unwrap();
-            let error = format!("<p>Unable to open file: {}</p> <p>error: {}</p>", filepath, error_msg);
-            return Err(error)
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &filepath, error))
         }
 
         let file = boxed_open.unwrap();
@@ -60,28 +74,138 @@ impl FileExtImpl {
         if boxed_seek.is_ok() {
             let boxed_read = reader.take(buff_length).read_to_end(&mut file_content);
             if boxed_read.is_err() {
-                let error_msg = boxed_read.err().unwrap().to_string();
-                let error = format!("<p>Unable to read file: {}</p> <p>error: {}</p>", filepath, error_msg);
-                return Err(error)
+                let error = boxed_read.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Read, &filepath, error))
             }
         } else {
-            let error_msg = boxed_seek.err().unwrap().to_string();
-            let error = format!("<p>Unable to seek file: {}</p> <p>error: {}</p>", filepath, error_msg);
-            return Err(error)
+            let error = boxed_seek.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Seek, &filepath, error))
         }
 
         Ok(file_content)
     }
 
-    pub fn read_or_create_and_write(path: &str, content: &[u8]) -> Result<Vec<u8>, String> {
-        let boxed_check = FilterString::is_valid_input_string(path);
+    pub fn read_file_ranges<P: AsRef<Path>>(path: P, range_list: &[ByteRange]) -> Result<Vec<ByteRangeContent>, FileExtError> {
+        let boxed_read = FileExtImpl::read_ranges(path, range_list, ByteRange::MAX_TOTAL_LENGTH);
+        if boxed_read.is_err() {
+            let error = boxed_read.err().unwrap();
+            return Err(error)
+        }
+
+        let (_file_length, range_content_list) = boxed_read.unwrap();
+        Ok(range_content_list)
+    }
+
+    pub fn read_file_ranges_as_multipart<P: AsRef<Path>>(path: P, range_list: &[ByteRange], content_type: &str, boundary: &str) -> Result<Vec<u8>, FileExtError> {
+        let boxed_read = FileExtImpl::read_ranges(path, range_list, ByteRange::MAX_TOTAL_LENGTH);
+        if boxed_read.is_err() {
+            let error = boxed_read.err().unwrap();
+            return Err(error)
+        }
+
+        let (file_length, range_content_list) = boxed_read.unwrap();
+        Ok(ByteRange::multipart_body(&range_content_list, file_length, content_type, boundary))
+    }
+
+    fn read_ranges<P: AsRef<Path>>(path: P, range_list: &[ByteRange], max_total_length: u64) -> Result<(u64, Vec<ByteRangeContent>), FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::Read, &filepath, message))
+        }
+
+        let boxed_open = File::open(path);
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &filepath, error))
+        }
+        let mut file = boxed_open.unwrap();
+
+        let boxed_metadata = file.metadata();
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &filepath, error))
+        }
+        let file_length = boxed_metadata.unwrap().len();
+
+        let resolved_range_list = ByteRange::resolve_and_coalesce(range_list, file_length);
+        if resolved_range_list.is_empty() {
+            let message = format!("none of the ranges can be satisfied, file length is {}", file_length);
+            return Err(FileExtError::from_kind(Operation::Read, &filepath, ErrorKind::InvalidInput, message))
+        }
+
+        // coalesced ranges do not overlap, so the sum never exceeds file length
+        let total_length: u64 = resolved_range_list.iter().map(|(start, end)| end - start + 1).sum();
+        if total_length > max_total_length {
+            let message = format!("ranges are {} bytes in total, at most {} bytes can be read at once", total_length, max_total_length);
+            return Err(FileExtError::from_kind(Operation::Read, &filepath, ErrorKind::InvalidInput, message))
+        }
+
+        let mut range_content_list = vec![];
+        for (start, end) in resolved_range_list {
+            let boxed_length = usize::try_from(end - start + 1);
+            if boxed_length.is_err() {
+                let message = format!("range {}-{} does not fit into memory on this platform", start, end);
+                return Err(FileExtError::from_kind(Operation::Read, &filepath, ErrorKind::InvalidInput, message))
+            }
+            let length = boxed_length.unwrap();
+
+            let boxed_seek = file.seek(SeekFrom::Start(start));
+            if boxed_seek.is_err() {
+                let error = boxed_seek.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Seek, &filepath, error))
+            }
+
+            let mut content = vec![0; length];
+            let boxed_read = file.read_exact(&mut content);
+            if boxed_read.is_err() {
+                let error = boxed_read.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Read, &filepath, error))
+            }
+
+            range_content_list.push(ByteRangeContent { start, end, content });
+        }
+
+        Ok((file_length, range_content_list))
+    }
+
+    pub fn open_reader<P: AsRef<Path>>(path: P, chunk_size: Option<usize>) -> Result<FileReader, FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::Read, &filepath, message))
+        }
+
+        let chunk_size = chunk_size.unwrap_or(FileReader::DEFAULT_CHUNK_SIZE);
+        if chunk_size == 0 {
+            let message = "chunk size must be greater than 0".to_string();
+            return Err(FileExtError::from_kind(Operation::Read, &filepath, ErrorKind::InvalidInput, message))
+        }
+
+        let boxed_open = File::open(path);
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &filepath, error))
+        }
+
+        let file = boxed_open.unwrap();
+        Ok(FileReader::new(&filepath, file, chunk_size))
+    }
+
+    pub fn read_or_create_and_write<P: AsRef<Path>>(path: P, content: &[u8]) -> Result<Vec<u8>, FileExtError> {
+        let path = path.as_ref();
+        let boxed_check = FilterString::is_valid_input_path(path);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::Read, &path.to_string_lossy(), message))
         }
 
         let does_file_exist = Self::does_file_exist(path);
-        return if does_file_exist {
+        if does_file_exist {
             let boxed_read = Self::read_file(path);
             if boxed_read.is_err() {
                 return Err(boxed_read.err().unwrap());
@@ -91,170 +215,362 @@ impl FileExtImpl {
         } else {
             let boxed_create = Self::create_file(path);
             if boxed_create.is_err() {
-                let message = boxed_create.err().unwrap();
-                return Err(message)
+                let error = boxed_create.err().unwrap();
+                return Err(error)
             }
 
             let boxed_write = Self::write_file(path, content);
             if boxed_write.is_err() {
-                let message = boxed_write.err().unwrap();
-                return Err(message)
+                let error = boxed_write.err().unwrap();
+                return Err(error)
             }
             Ok(Vec::from(content))
         }
     }
 
-    pub fn write_file(path: &str, file_content: &[u8]) -> Result<(), String> {
-        let boxed_check = FilterString::is_valid_input_string(path);
+    pub fn write_file<P: AsRef<Path>>(path: P, file_content: &[u8]) -> Result<(), FileExtError> {
+        FileExtImpl::write_file_with_mode(path, file_content, WriteMode::Append)
+    }
+
+    pub fn write_file_with_mode<P: AsRef<Path>>(path: P, file_content: &[u8], mode: WriteMode) -> Result<(), FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::Write, &filepath, message))
         }
 
-        let mut file = OpenOptions::new()
-            .read(false)
-            .write(true)
-            .create(false)
-            .truncate(false)
-            .open(path)
-            .unwrap();
+        let boxed_open = mode.open_options().open(path);
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &filepath, error))
+        }
 
-        let boxed_seek = file.seek(SeekFrom::End(0));
-        if boxed_seek.is_err() {
-            let message = boxed_seek.err().unwrap().to_string();
-            return Err(message);
+        let mut file = boxed_open.unwrap();
+        let boxed_write = file.write_all(file_content);
+        if boxed_write.is_err() {
+            let error = boxed_write.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Write, &filepath, error))
+        }
+        Ok(())
+    }
+
+    pub fn write_file_atomically<P: AsRef<Path>>(path: P, file_content: &[u8]) -> Result<(), FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::Write, &filepath, message))
+        }
+
+        // renaming over a symlink would replace the link itself, so the file it points to is replaced instead
+        let boxed_target = FileExtImpl::resolve_symlink_destination(path);
+        if boxed_target.is_err() {
+            let error = boxed_target.err().unwrap();
+            return Err(error)
+        }
+        let resolved_target = boxed_target.unwrap();
+        let target = resolved_target.as_path();
+
+        let boxed_file_name = target.file_name();
+        if boxed_file_name.is_none() {
+            let message = "path does not point to a file".to_string();
+            return Err(FileExtError::from_kind(Operation::Write, &filepath, ErrorKind::InvalidInput, message))
+        }
+        let file_name = boxed_file_name.unwrap();
+
+        let mut directory = target.parent().unwrap_or(Path::new(".")).to_path_buf();
+        if directory.as_os_str().is_empty() {
+            directory = Path::new(".").to_path_buf();
+        }
+
+        // temp file is created in the same directory, so rename does not cross filesystem boundary
+        let mut temp_file_name = OsString::from(".");
+        temp_file_name.push(file_name);
+        temp_file_name.push(format!(".{}.{}.tmp", std::process::id(), DateTimeExt::_now_unix_epoch_nanos()));
+        let temp_path = directory.join(temp_file_name);
+
+        let permissions = fs::metadata(target).ok().map(|metadata| metadata.permissions());
+        let boxed_write = FileExtImpl::write_and_sync(&temp_path, file_content, permissions);
+        if boxed_write.is_err() {
+            let error = boxed_write.err().unwrap();
+            fs::remove_file(&temp_path).unwrap_or_default();
+            return Err(error)
+        }
+
+        let boxed_rename = fs::rename(&temp_path, target);
+        if boxed_rename.is_err() {
+            let error = boxed_rename.err().unwrap();
+            fs::remove_file(&temp_path).unwrap_or_default();
+            return Err(FileExtError::from_io(Operation::Rename, &filepath, error))
         }
 
-        boxed_seek.unwrap();
+        FileExtImpl::sync_directory(directory.as_path())
+    }
+
+    /// Follows symlinks until a regular file or a missing entry is reached, relative targets are resolved
+    /// against the directory of the link. Dangling link resolves to the path its target is to be created at
+    fn resolve_symlink_destination(path: &Path) -> Result<PathBuf, FileExtError> {
+        let mut current = path.to_path_buf();
+        for _ in 0..SymlinkChain::MAX_DEPTH {
+            let is_symlink = fs::symlink_metadata(&current)
+                .map(|metadata| metadata.file_type().is_symlink())
+                .unwrap_or(false);
+            if !is_symlink {
+                return Ok(current)
+            }
+
+            let boxed_points_to = SymlinkExtImpl::symlink_points_to_path(&current);
+            if boxed_points_to.is_err() {
+                let error = boxed_points_to.err().unwrap();
+                return Err(error)
+            }
+
+            // joining an absolute target replaces the base
+            let symlink_directory = current.parent().unwrap_or(Path::new("")).to_path_buf();
+            current = symlink_directory.join(boxed_points_to.unwrap());
+        }
+
+        let message = format!("too many levels of symbolic links, limit is {}", SymlinkChain::MAX_DEPTH);
+        Err(FileExtError::from_kind(Operation::ResolveSymlink, &path.to_string_lossy(), ErrorKind::InvalidData, message))
+    }
 
+    fn write_and_sync(path: &Path, file_content: &[u8], permissions: Option<Permissions>) -> Result<(), FileExtError> {
+        let filepath = path.to_string_lossy();
+        let boxed_open = FileExtImpl::create_new_file_with_permissions(path, permissions.as_ref());
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Create, &filepath, error))
+        }
+
+        let mut file = boxed_open.unwrap();
         let boxed_write = file.write_all(file_content);
         if boxed_write.is_err() {
-            let message = format!("unable to write to file: {}", boxed_write.err().unwrap());
-            return Err(message)
+            let error = boxed_write.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Write, &filepath, error))
+        }
+
+        let boxed_sync = file.sync_all();
+        if boxed_sync.is_err() {
+            let error = boxed_sync.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Write, &filepath, error))
+        }
+
+        // read-only flag is the only permission outside of unix, it is set after the content is written
+        #[cfg(not(target_family = "unix"))]
+        if let Some(permissions) = permissions {
+            fs::set_permissions(path, permissions).unwrap_or_default();
+        }
+
+        Ok(())
+    }
+
+    /// File is created with the given mode, it is never readable by anyone the permissions do not allow
+    #[cfg(target_family = "unix")]
+    pub(crate) fn create_new_file_with_permissions(path: &Path, permissions: Option<&Permissions>) -> io::Result<File> {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let mut open_options = WriteMode::CreateNew.open_options();
+        if let Some(permissions) = permissions {
+            open_options.mode(permissions.mode() & 0o7777);
+        }
+
+        let boxed_open = open_options.open(path);
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(error)
+        }
+        let file = boxed_open.unwrap();
+
+        // umask may have removed some bits, mode is set exactly before any content is written
+        if let Some(permissions) = permissions {
+            let boxed_permissions = file.set_permissions(permissions.clone());
+            if boxed_permissions.is_err() {
+                let error = boxed_permissions.err().unwrap();
+                return Err(error)
+            }
         }
+
+        Ok(file)
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    pub(crate) fn create_new_file_with_permissions(path: &Path, _permissions: Option<&Permissions>) -> io::Result<File> {
+        WriteMode::CreateNew.open_options().open(path)
+    }
+
+    #[cfg(target_family = "unix")]
+    pub(crate) fn sync_directory(directory: &Path) -> Result<(), FileExtError> {
+        let boxed_open = File::open(directory);
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &directory.to_string_lossy(), error))
+        }
+
+        let boxed_sync = boxed_open.unwrap().sync_all();
+        if boxed_sync.is_err() {
+            let error = boxed_sync.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Write, &directory.to_string_lossy(), error))
+        }
+
         Ok(())
     }
 
-    pub fn create_file(path: &str) -> Result<(), String>  {
-        let boxed_check = FilterString::is_valid_input_string(path);
+    // directories can not be opened as files on windows and wasi, rename is persisted by the filesystem itself
+    #[cfg(not(target_family = "unix"))]
+    pub(crate) fn sync_directory(_directory: &Path) -> Result<(), FileExtError> {
+        Ok(())
+    }
+
+    pub fn create_file<P: AsRef<Path>>(path: P) -> Result<(), FileExtError>  {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::Create, &filepath, message))
         }
 
         let boxed_file = File::create(path);
 
         if boxed_file.is_err() {
-            let message = format!("unable to create file: {}", boxed_file.err().unwrap());
-            return Err(message)
+            let error = boxed_file.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Create, &filepath, error))
         }
 
         boxed_file.unwrap();
         Ok(())
     }
 
-    pub fn does_file_exist(path: &str) -> bool {
-        let file_exists = Path::new(path).is_file();
+    pub fn does_file_exist<P: AsRef<Path>>(path: P) -> bool {
+        let file_exists = path.as_ref().is_file();
         file_exists
     }
 
-    pub fn delete_file(path: &str) -> Result<(), String> {
-        let boxed_check = FilterString::is_valid_input_string(path);
+    pub fn delete_file<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_check = FilterString::is_valid_input_path(path);
         if boxed_check.is_err() {
             let message = boxed_check.err().unwrap();
-            return Err(message)
+            return Err(FileExtError::invalid_path(Operation::Delete, &filepath, message))
         }
         
         let boxed_remove = fs::remove_file(path);
         if boxed_remove.is_err() {
-            let msg = boxed_remove.err().unwrap().to_string();
-            return Err(msg)
+            let error = boxed_remove.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Delete, &filepath, error))
         }
 
         Ok(())
     }
 
-    pub fn copy_part_of_file(from: Vec<&str>, to: Vec<&str>, start: u64, end: u64) -> Result<(), String> {
-        let from_path = FileExt::build_path(&from);
-        let file_exists = FileExt::does_file_exist(from_path.as_str());
-        if !file_exists {
-            let message = format!("file at given path {} does not exist", from_path.as_str());
-            return Err(message);
+    /// Creates a new empty file with a unique name in the temp folder, readable and writable only by the owner on Unix.
+    /// Existing files are never opened, name collision means another attempt with a different name
+    pub fn create_temp_file(prefix: &str, suffix: &str) -> Result<TempFile, FileExtError> {
+        let boxed_temp_folder = PathExtImpl::get_temp_folder_path();
+        if boxed_temp_folder.is_err() {
+            let error = boxed_temp_folder.err().unwrap();
+            return Err(error)
         }
+        let temp_folder = boxed_temp_folder.unwrap();
 
-
-        let boxed_content_to_copy = FileExt::read_file_partially(from_path.as_str(), start, end);
-        if boxed_content_to_copy.is_err() {
-            let message = boxed_content_to_copy.err().unwrap();
-            return Err(message);
+        for affix in [prefix, suffix] {
+            let boxed_check = temp_entry::check_affix(Operation::Create, affix);
+            if boxed_check.is_err() {
+                let error = boxed_check.err().unwrap();
+                return Err(error)
+            }
         }
-        let content_to_copy = boxed_content_to_copy.unwrap();
 
-
-        let to_path = FileExt::build_path(&to);
-        if !FileExt::does_file_exist(to_path.as_str()) {
-            let boxed_create = FileExt::create_file(to_path.as_str());
+        for _attempt in 0..temp_entry::MAX_ATTEMPT_COUNT {
+            let path = temp_entry::random_path(&temp_folder, prefix, suffix);
+            let boxed_create = FileExtImpl::create_new_private_file(&path);
             if boxed_create.is_err() {
-                let message = boxed_create.err().unwrap();
-                return Err(message);
+                let error = boxed_create.err().unwrap();
+                if error.kind() == ErrorKind::AlreadyExists {
+                    continue;
+                }
+                return Err(FileExtError::from_io(Operation::Create, &path, error))
             }
+
+            return Ok(TempFile::new(path))
         }
 
+        let message = format!("unable to find unique file name after {} attempts", temp_entry::MAX_ATTEMPT_COUNT);
+        Err(FileExtError::from_kind(Operation::Create, &temp_folder, ErrorKind::AlreadyExists, message))
+    }
 
-        let boxed_write =
-            FileExt::write_file(to_path.as_str(), content_to_copy.as_slice());
-        if boxed_write.is_err() {
-            let message = boxed_write.err().unwrap();
-            return Err(message);
-        }
-        Ok(boxed_write.unwrap())
+    #[cfg(target_family = "unix")]
+    fn create_new_private_file(path: &str) -> io::Result<File> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        WriteMode::CreateNew.open_options().mode(0o600).open(path)
     }
 
-    pub fn copy_file(from: Vec<&str>, to: Vec<&str>)-> Result<(), String> {
-        let boxed_length = FileExtImpl::file_length(from.clone());
-        if boxed_length.is_err() {
-            let message = boxed_length.err().unwrap();
-            return Err(message);
-        }
+    #[cfg(not(target_family = "unix"))]
+    fn create_new_private_file(path: &str) -> io::Result<File> {
+        WriteMode::CreateNew.open_options().open(path)
+    }
 
-        let file_length = boxed_length.unwrap();
-        let _100kb = 102400;
-        let step = _100kb;
-        let mut start = 0;
-        let mut end = step;
-        if step >= file_length {
-            end = file_length - 1;
-        }
-
-        let mut continue_copying = true;
-        while continue_copying {
-            let boxed_copy = FileExtImpl::copy_part_of_file(
-                from.clone(),
-                to.clone(),
-                start,
-                end
-            );
+    pub fn file_modified_utc<P: AsRef<Path>>(path: P) -> Result<u128, FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy();
+        let boxed_open = File::open(path);
+        if boxed_open.is_err() {
+            let error = boxed_open.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &filepath, error))
+        }
 
-            if boxed_copy.is_err() {
-                let message = boxed_copy.err().unwrap();
-                return Err(message);
-            }
+        let file : File = boxed_open.unwrap();
+        let boxed_metadata = file.metadata();
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &filepath, error))
+        }
+        let metadata = boxed_metadata.unwrap();
+        let boxed_last_modified_time = metadata.modified();
+        if boxed_last_modified_time.is_err() {
+            let error = boxed_last_modified_time.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &filepath, error))
+        }
+        let modified_time = boxed_last_modified_time.unwrap();
+        let nanos = DateTimeExt::_system_time_to_unix_nanos(modified_time);
+        Ok(nanos)
+    }
 
-            boxed_copy.unwrap();
+    pub fn copy_file(from: Vec<&str>, to: Vec<&str>)-> Result<(), FileExtError> {
+        let from_path = FileExt::build_path(&from);
+        let to_path = FileExt::build_path(&to);
+        FileExtImpl::copy_path(from_path, to_path)
+    }
 
-            if end == file_length - 1 {
-                continue_copying = false;
-            } else {
-                start = end + 1;
-                end = end + step;
-                if start + step >= file_length {
-                    end = file_length - 1;
-                }
-            }
+    pub fn copy_path<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q)-> Result<(), FileExtError> {
+        FileExtImpl::copy_path_with_callbacks(from, to, None, |_, _, _| {}, |_, _, _| false)
+    }
 
+    /// Same as `copy_file_with_callbacks`, source and destination are given as OS paths
+    pub fn copy_path_with_callbacks
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        block_size: Option<u64>,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<(), FileExtError> {
+        let from = from.as_ref();
+        let boxed_length = FileExtImpl::file_length_path(from);
+        if boxed_length.is_err() {
+            let error = boxed_length.err().unwrap();
+            return Err(error)
         }
 
-        Ok(())
+        let file_length = boxed_length.unwrap();
+        FileExtImpl::copy_path_range_with_callbacks(from, to, FileRange::whole(file_length), block_size, progress_callback, cancel_callback)
     }
 
     pub fn copy_file_with_callbacks
@@ -266,7 +582,7 @@ impl FileExtImpl {
                     progress_callback: F,
                     cancel_callback: C,
                 )
-        -> Result<(), String> {
+        -> Result<(), FileExtError> {
         FileExtImpl::copy_file_with_callbacks_starting_from_byte(from, to, 0, block_size, progress_callback, cancel_callback)
     }
 
@@ -280,16 +596,22 @@ impl FileExtImpl {
         progress_callback: F,
         cancel_callback: C,
     )
-        -> Result<(), String> {
+        -> Result<(), FileExtError> {
         let boxed_length = FileExtImpl::file_length(from.clone());
         if boxed_length.is_err() {
-            let message = boxed_length.err().unwrap();
-            return Err(message);
+            let error = boxed_length.err().unwrap();
+            return Err(error);
         }
 
         let file_length = boxed_length.unwrap();
-        let ending_byte = file_length - 1;
-        FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(from, to, starting_byte, ending_byte, block_size, progress_callback, cancel_callback)
+        let boxed_range = FileRange::new(starting_byte, file_length);
+        if boxed_range.is_err() {
+            let error = boxed_range.err().unwrap();
+            return Err(FileExtImpl::with_copy_path(error, &FileExt::build_path(&from)));
+        }
+
+        let range = boxed_range.unwrap();
+        FileExtImpl::copy_file_range_with_callbacks(from, to, range, block_size, progress_callback, cancel_callback)
     }
 
 
@@ -301,74 +623,150 @@ impl FileExtImpl {
         starting_byte: u64,
         ending_byte: u64,
         block_size: Option<u64>,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<(), FileExtError> {
+        let boxed_range = FileRange::inclusive(starting_byte, ending_byte);
+        if boxed_range.is_err() {
+            let error = boxed_range.err().unwrap();
+            return Err(FileExtImpl::with_copy_path(error, &FileExt::build_path(&from)));
+        }
+
+        let range = boxed_range.unwrap();
+        FileExtImpl::copy_file_range_with_callbacks(from, to, range, block_size, progress_callback, cancel_callback)
+    }
+
+    /// Copies bytes of the source in the given range to the end of destination,
+    /// destination is created if it does not exist. Both files are opened once.
+    /// Callbacks receive offsets of the first and the last (inclusive) byte of the block and the end of the range.
+    pub fn copy_file_range_with_callbacks
+    <F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: Vec<&str>,
+        to: Vec<&str>,
+        range: FileRange,
+        block_size: Option<u64>,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<(), FileExtError> {
+        let from_path = FileExt::build_path(&from);
+        let to_path = FileExt::build_path(&to);
+        FileExtImpl::copy_path_range_with_callbacks(from_path, to_path, range, block_size, progress_callback, cancel_callback)
+    }
+
+    /// Same as `copy_file_range_with_callbacks`, source and destination are given as OS paths
+    pub fn copy_path_range_with_callbacks
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        range: FileRange,
+        block_size: Option<u64>,
         mut progress_callback: F,
         mut cancel_callback: C,
     )
-        -> Result<(), String> {
-        let boxed_length = FileExtImpl::file_length(from.clone());
-        if boxed_length.is_err() {
-            let message = boxed_length.err().unwrap();
-            return Err(message);
+        -> Result<(), FileExtError> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        let from_path = from.to_string_lossy();
+        let to_path = to.to_string_lossy();
+
+        let _100kb = 102400;
+        let step = block_size.unwrap_or(_100kb);
+        if step == 0 {
+            let message = "block size must be greater than 0".to_string();
+            return Err(FileExtError::from_kind(Operation::Copy, &from_path, ErrorKind::InvalidInput, message));
         }
 
-        let file_length = boxed_length.unwrap();
-        if (file_length - 1) < ending_byte {
-            let message = format!("file length is {}, ending byte is {}", file_length, ending_byte);
-            return Err(message);
+        let boxed_check = FilterString::is_valid_input_path(from);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::Copy, &from_path, message))
         }
 
-        let _100kb = 102400;
-        let mut step = _100kb;
-        if block_size.is_some() {
-            step = block_size.unwrap();
-        }
-        let mut start = starting_byte;
-        let mut end = start + step;
-        if step >= ending_byte {
-            end = ending_byte - 1;
-        }
-
-        let mut continue_copying = true;
-        while continue_copying {
-            progress_callback(start, end, ending_byte);
-            let boxed_copy = FileExtImpl::copy_part_of_file(
-                from.clone(),
-                to.clone(),
-                start,
-                end
-            );
+        let boxed_check = FilterString::is_valid_input_path(to);
+        if boxed_check.is_err() {
+            let message = boxed_check.err().unwrap();
+            return Err(FileExtError::invalid_path(Operation::Copy, &to_path, message))
+        }
 
-            if boxed_copy.is_err() {
-                let message = boxed_copy.err().unwrap();
-                return Err(message);
-            }
+        let boxed_source = File::open(from);
+        if boxed_source.is_err() {
+            let error = boxed_source.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &from_path, error))
+        }
+        let mut source = boxed_source.unwrap();
 
-            boxed_copy.unwrap();
+        let boxed_metadata = source.metadata();
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &from_path, error))
+        }
 
-            let copying_cancelled_by_user = cancel_callback(start, end, ending_byte);
-            let reached_end_of_file = end == ending_byte - 1;
+        let file_length = boxed_metadata.unwrap().len();
+        let boxed_check = range.check_within(file_length);
+        if boxed_check.is_err() {
+            let error = boxed_check.err().unwrap();
+            return Err(FileExtImpl::with_copy_path(error, &from_path))
+        }
 
-            if reached_end_of_file || copying_cancelled_by_user {
-                continue_copying = false;
-            } else {
-                start = end + 1;
-                end = end + step;
-                if start + step >= ending_byte {
-                    end = ending_byte - 1;
-                }
+        let boxed_destination = WriteMode::CreateOrAppend.open_options().open(to);
+        if boxed_destination.is_err() {
+            let error = boxed_destination.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, &to_path, error))
+        }
+        let mut destination = boxed_destination.unwrap();
+
+        let boxed_seek = source.seek(SeekFrom::Start(range.start()));
+        if boxed_seek.is_err() {
+            let error = boxed_seek.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Seek, &from_path, error))
+        }
+
+        for block in range.blocks(step) {
+            let last_byte = block.end() - 1;
+            progress_callback(block.start(), last_byte, range.end());
+
+            // io::copy reuses a single buffer and on linux delegates to copy_file_range/sendfile
+            let mut block_reader = Read::take(&source, block.len());
+            let boxed_copy = io::copy(&mut block_reader, &mut destination);
+            if boxed_copy.is_err() {
+                let error = boxed_copy.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Copy, &from_path, error))
             }
 
+            let copied_length = boxed_copy.unwrap();
+            if copied_length != block.len() {
+                let message = format!("expected to copy {} bytes starting from byte {}, copied {}", block.len(), block.start(), copied_length);
+                return Err(FileExtError::from_kind(Operation::Copy, &from_path, ErrorKind::UnexpectedEof, message))
+            }
+
+            let copying_cancelled_by_user = cancel_callback(block.start(), last_byte, range.end());
+            if copying_cancelled_by_user {
+                break;
+            }
         }
 
         Ok(())
     }
 
-    pub fn file_length(path: Vec<&str>) -> Result<u64, String> {
+    fn with_copy_path(error: FileExtError, from_path: &str) -> FileExtError {
+        FileExtError::from_kind(Operation::Copy, from_path, error.kind(), error.message().to_string())
+    }
+
+    pub fn file_length(path: Vec<&str>) -> Result<u64, FileExtError> {
         let filepath = FileExt::build_path(path.as_slice());
-        let boxed_length = fs::metadata(filepath);
+        FileExtImpl::file_length_path(filepath)
+    }
+
+    pub fn file_length_path<P: AsRef<Path>>(path: P) -> Result<u64, FileExtError> {
+        let path = path.as_ref();
+        let boxed_length = fs::metadata(path);
         if boxed_length.is_err() {
-            let message = boxed_length.err().unwrap().to_string();
-            return Err(message)
+            let error = boxed_length.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &path.to_string_lossy(), error))
         }
         let length = boxed_length.unwrap().len();
         Ok(length)
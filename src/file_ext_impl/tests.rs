@@ -1,6 +1,10 @@
+use std::io::ErrorKind;
+use crate::byte_range::ByteRange;
 use crate::file_ext_impl::FileExtImpl;
+use crate::file_range::FileRange;
 use crate::FileExt;
 use crate::path_ext_impl::PathExtImpl;
+use crate::write_mode::WriteMode;
 
 #[test]
 fn write() {
@@ -180,3 +184,392 @@ fn copy_file_with_callback_and_block_size_starting_from_byte_and_ending_byte() {
     let path = FileExt::build_path(vec![pwd.as_str(), "LICENSE_copy5"].as_slice());
     FileExt::delete_file(path.as_str()).unwrap();
 }
+
+#[test]
+fn read_file_ranges() {
+    let path = "test/index.html";
+    let file_content = FileExtImpl::read_file(path).unwrap();
+    let file_length = file_content.len() as u64;
+
+    let range_list = ByteRange::parse_header("bytes=4-10,8-12,-7,5000-").unwrap();
+    let range_content_list = FileExtImpl::read_file_ranges(path, &range_list).unwrap();
+
+    assert_eq!(2, range_content_list.len());
+
+    assert_eq!(4, range_content_list[0].start);
+    assert_eq!(12, range_content_list[0].end);
+    assert_eq!(&file_content[4..=12], range_content_list[0].content.as_slice());
+
+    assert_eq!(file_length - 7, range_content_list[1].start);
+    assert_eq!(file_length - 1, range_content_list[1].end);
+    assert_eq!("</html>".as_bytes(), range_content_list[1].content.as_slice());
+}
+
+#[test]
+fn read_file_ranges_not_satisfiable() {
+    let path = "test/index.html";
+    let range_list = ByteRange::parse_header("bytes=5000-6000").unwrap();
+    let boxed_read = FileExtImpl::read_file_ranges(path, &range_list);
+    assert_eq!(ErrorKind::InvalidInput, boxed_read.err().unwrap().kind());
+}
+
+#[test]
+fn read_file_ranges_total_length_limit() {
+    let path = "test/index.html";
+    let range_list = ByteRange::parse_header("bytes=0-9,20-29").unwrap();
+
+    let boxed_read = FileExtImpl::read_ranges(path, &range_list, 19);
+    assert_eq!(ErrorKind::InvalidInput, boxed_read.err().unwrap().kind());
+
+    let (_, range_content_list) = FileExtImpl::read_ranges(path, &range_list, 20).unwrap();
+    assert_eq!(2, range_content_list.len());
+}
+
+#[test]
+fn read_file_ranges_as_multipart() {
+    let path = "test/index.html";
+    let file_length = FileExtImpl::read_file(path).unwrap().len();
+
+    let range_list = [ByteRange::Inclusive { start: 4, end: 10 }, ByteRange::Suffix { length: 7 }];
+    let body = FileExtImpl::read_file_ranges_as_multipart(path, &range_list, "text/html", "3d6b6a416f9b5").unwrap();
+
+    let expected_body = format!(
+        "--3d6b6a416f9b5\r\nContent-Type: text/html\r\nContent-Range: bytes 4-10/{}\r\n\r\nCTYPE h\r\n--3d6b6a416f9b5\r\nContent-Type: text/html\r\nContent-Range: bytes {}-{}/{}\r\n\r\n</html>\r\n--3d6b6a416f9b5--\r\n",
+        file_length,
+        file_length - 7,
+        file_length - 1,
+        file_length,
+    );
+    assert_eq!(expected_body, String::from_utf8(body).unwrap());
+}
+
+#[test]
+fn write_file_does_not_exist() {
+    let path = "test/write_file-does-not-exist.txt";
+    let boxed_write = FileExtImpl::write_file(path, "data".as_bytes());
+    assert_eq!(ErrorKind::NotFound, boxed_write.err().unwrap().kind());
+    assert!(!FileExtImpl::does_file_exist(path));
+}
+
+#[test]
+fn write_file_with_mode() {
+    let path = "test/write_file_with_mode.txt";
+
+    FileExtImpl::write_file_with_mode(path, "data".as_bytes(), WriteMode::CreateNew).unwrap();
+    let boxed_write = FileExtImpl::write_file_with_mode(path, "data".as_bytes(), WriteMode::CreateNew);
+    assert_eq!(ErrorKind::AlreadyExists, boxed_write.err().unwrap().kind());
+
+    FileExtImpl::write_file_with_mode(path, " appended".as_bytes(), WriteMode::Append).unwrap();
+    assert_eq!("data appended".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    FileExtImpl::write_file_with_mode(path, "overwritten".as_bytes(), WriteMode::Overwrite).unwrap();
+    assert_eq!("overwritten".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    FileExtImpl::write_file_with_mode(path, "!".as_bytes(), WriteMode::CreateOrAppend).unwrap();
+    assert_eq!("overwritten!".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    FileExtImpl::delete_file(path).unwrap();
+
+    FileExtImpl::write_file_with_mode(path, "created".as_bytes(), WriteMode::CreateOrAppend).unwrap();
+    assert_eq!("created".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    FileExtImpl::write_file_with_mode(path, "truncated".as_bytes(), WriteMode::CreateOrTruncate).unwrap();
+    assert_eq!("truncated".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    FileExtImpl::delete_file(path).unwrap();
+
+    let boxed_write = FileExtImpl::write_file_with_mode(path, "data".as_bytes(), WriteMode::Overwrite);
+    assert_eq!(ErrorKind::NotFound, boxed_write.err().unwrap().kind());
+}
+
+#[test]
+fn write_file_atomically() {
+    let path = "test/write_file_atomically.txt";
+
+    FileExtImpl::write_file_atomically(path, "version 1".as_bytes()).unwrap();
+    assert_eq!("version 1".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    FileExtImpl::write_file_atomically(path, "version 2".as_bytes()).unwrap();
+    assert_eq!("version 2".as_bytes(), FileExtImpl::read_file(path).unwrap());
+
+    let temp_file_left = std::fs::read_dir("test").unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .any(|name| name.starts_with(".write_file_atomically.txt."));
+    assert!(!temp_file_left);
+
+    FileExtImpl::delete_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn write_file_atomically_preserves_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = "test/write_file_atomically_permissions.txt";
+    FileExtImpl::write_file_with_mode(path, "data".as_bytes(), WriteMode::CreateNew).unwrap();
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+    FileExtImpl::write_file_atomically(path, "new data".as_bytes()).unwrap();
+
+    let mode = std::fs::metadata(path).unwrap().permissions().mode();
+    assert_eq!(0o640, mode & 0o777);
+
+    FileExtImpl::delete_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn write_file_atomically_never_exposes_private_content() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = "test/write_file_atomically_private.txt";
+    FileExtImpl::write_file_with_mode(path, "secret".as_bytes(), WriteMode::CreateNew).unwrap();
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    let permissions = std::fs::metadata(path).unwrap().permissions();
+
+    // temp file has the target mode from the moment it appears, before any content is written
+    let temp_path = std::path::Path::new("test/.write_file_atomically_private.txt.tmp");
+    let file = FileExtImpl::create_new_file_with_permissions(temp_path, Some(&permissions)).unwrap();
+    assert_eq!(0o600, file.metadata().unwrap().permissions().mode() & 0o777);
+    assert_eq!(0o600, std::fs::metadata(temp_path).unwrap().permissions().mode() & 0o777);
+    drop(file);
+    std::fs::remove_file(temp_path).unwrap();
+
+    FileExtImpl::write_file_atomically(path, "new secret".as_bytes()).unwrap();
+    assert_eq!(0o600, std::fs::metadata(path).unwrap().permissions().mode() & 0o777);
+
+    FileExtImpl::delete_file(path).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn write_file_atomically_through_symlink() {
+    let directory = "test/write_file_atomically_symlink";
+    std::fs::create_dir_all(format!("{}/releases", directory)).unwrap();
+    let target = format!("{}/releases/config.toml", directory);
+    let link = format!("{}/config.toml", directory);
+    let dangling_link = format!("{}/next.toml", directory);
+    FileExtImpl::write_file_atomically(&target, "version 1".as_bytes()).unwrap();
+    std::os::unix::fs::symlink("releases/config.toml", &link).unwrap();
+    std::os::unix::fs::symlink("releases/next.toml", &dangling_link).unwrap();
+
+    FileExtImpl::write_file_atomically(&link, "version 2".as_bytes()).unwrap();
+    assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+    assert_eq!("version 2".as_bytes(), FileExtImpl::read_file(&target).unwrap());
+
+    FileExtImpl::write_file_atomically(&dangling_link, "next".as_bytes()).unwrap();
+    assert!(std::fs::symlink_metadata(&dangling_link).unwrap().file_type().is_symlink());
+    assert_eq!("next".as_bytes(), FileExtImpl::read_file(format!("{}/releases/next.toml", directory)).unwrap());
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn write_file_atomically_readers_never_observe_partial_content() {
+    let path = "test/write_file_atomically_concurrent.txt";
+    let version_a = vec![b'a'; 512 * 1024];
+    let version_b = vec![b'b'; 256 * 1024];
+
+    FileExtImpl::write_file_atomically(path, &version_a).unwrap();
+
+    let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let reader_stop = stop.clone();
+    let expected_a = version_a.clone();
+    let expected_b = version_b.clone();
+    let reader = std::thread::spawn(move || {
+        let mut reads = 0;
+        while !reader_stop.load(std::sync::atomic::Ordering::SeqCst) {
+            let content = FileExtImpl::read_file(path).unwrap();
+            assert!(content == expected_a || content == expected_b, "observed partial content of {} bytes", content.len());
+            reads += 1;
+        }
+        reads
+    });
+
+    for index in 0..50 {
+        let content = if index % 2 == 0 { &version_b } else { &version_a };
+        FileExtImpl::write_file_atomically(path, content).unwrap();
+    }
+
+    stop.store(true, std::sync::atomic::Ordering::SeqCst);
+    let reads = reader.join().unwrap();
+    assert!(reads > 0);
+
+    FileExtImpl::delete_file(path).unwrap();
+}
+
+#[test]
+fn copy_file_content() {
+    let from = "test/copy_file_content-source.bin";
+    let to = "test/copy_file_content-destination.bin";
+    let content : Vec<u8> = (0..250_000u32).map(|index| (index % 251) as u8).collect();
+    FileExtImpl::write_file_with_mode(from, &content, WriteMode::CreateOrTruncate).unwrap();
+
+    FileExtImpl::copy_file(vec![from], vec![to]).unwrap();
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+
+    FileExtImpl::delete_file(from).unwrap();
+    FileExtImpl::delete_file(to).unwrap();
+}
+
+#[test]
+fn copy_file_with_callbacks_blocks_and_cancel() {
+    let from = "test/copy_file_with_callbacks_blocks-source.bin";
+    let to = "test/copy_file_with_callbacks_blocks-destination.bin";
+    let content : Vec<u8> = (0..1000u32).map(|index| (index % 251) as u8).collect();
+    FileExtImpl::write_file_with_mode(from, &content, WriteMode::CreateOrTruncate).unwrap();
+
+    let mut block_list = vec![];
+    FileExtImpl::copy_file_with_callbacks(
+        vec![from],
+        vec![to],
+        Some(100),
+        |start, end, _total| block_list.push((start, end)),
+        |_start, end, _total| end >= 299,
+    ).unwrap();
+
+    assert_eq!(vec![(0, 99), (100, 199), (200, 299)], block_list);
+    assert_eq!(&content[0..300], FileExtImpl::read_file(to).unwrap().as_slice());
+
+    FileExtImpl::delete_file(from).unwrap();
+    FileExtImpl::delete_file(to).unwrap();
+}
+
+fn create_copy_source(path: &str, length: u32) -> Vec<u8> {
+    let content : Vec<u8> = (0..length).map(|index| (index % 251) as u8).collect();
+    FileExtImpl::write_file_with_mode(path, &content, WriteMode::CreateOrTruncate).unwrap();
+    content
+}
+
+#[test]
+fn copy_empty_file() {
+    let from = "test/copy_empty_file-source.bin";
+    let to = "test/copy_empty_file-destination.bin";
+    create_copy_source(from, 0);
+
+    FileExtImpl::copy_file(vec![from], vec![to]).unwrap();
+    assert!(FileExtImpl::does_file_exist(to));
+    assert!(FileExtImpl::read_file(to).unwrap().is_empty());
+    FileExtImpl::delete_file(to).unwrap();
+
+    let mut callback_invoked = false;
+    FileExtImpl::copy_file_with_callbacks(
+        vec![from],
+        vec![to],
+        Some(10),
+        |_, _, _| callback_invoked = true,
+        |_, _, _| false,
+    ).unwrap();
+    assert!(!callback_invoked);
+    assert!(FileExtImpl::read_file(to).unwrap().is_empty());
+    FileExtImpl::delete_file(to).unwrap();
+
+    let boxed_copy = FileExtImpl::copy_file_with_callbacks_starting_from_byte(vec![from], vec![to], 1, None, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::InvalidInput, boxed_copy.err().unwrap().kind());
+
+    let boxed_copy = FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(vec![from], vec![to], 0, 0, None, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::InvalidInput, boxed_copy.err().unwrap().kind());
+    assert!(!FileExtImpl::does_file_exist(to));
+
+    FileExtImpl::delete_file(from).unwrap();
+}
+
+#[test]
+fn copy_one_byte_file() {
+    let from = "test/copy_one_byte_file-source.bin";
+    let to = "test/copy_one_byte_file-destination.bin";
+    let content = create_copy_source(from, 1);
+
+    FileExtImpl::copy_file(vec![from], vec![to]).unwrap();
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+    FileExtImpl::delete_file(to).unwrap();
+
+    let mut block_list = vec![];
+    FileExtImpl::copy_file_with_callbacks(
+        vec![from],
+        vec![to],
+        Some(10),
+        |start, end, total| block_list.push((start, end, total)),
+        |_, _, _| false,
+    ).unwrap();
+    assert_eq!(vec![(0, 0, 1)], block_list);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+    FileExtImpl::delete_file(to).unwrap();
+
+    FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(vec![from], vec![to], 0, 0, Some(1), |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+    FileExtImpl::delete_file(to).unwrap();
+
+    FileExtImpl::copy_file_with_callbacks_starting_from_byte(vec![from], vec![to], 1, None, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert!(FileExtImpl::read_file(to).unwrap().is_empty());
+    FileExtImpl::delete_file(to).unwrap();
+
+    FileExtImpl::delete_file(from).unwrap();
+}
+
+#[test]
+fn copy_block_size_larger_than_file() {
+    let from = "test/copy_block_size_larger_than_file-source.bin";
+    let to = "test/copy_block_size_larger_than_file-destination.bin";
+    let content = create_copy_source(from, 100);
+
+    let mut block_list = vec![];
+    FileExtImpl::copy_file_with_callbacks(
+        vec![from],
+        vec![to],
+        Some(1000),
+        |start, end, total| block_list.push((start, end, total)),
+        |_, _, _| false,
+    ).unwrap();
+    assert_eq!(vec![(0, 99, 100)], block_list);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+    FileExtImpl::delete_file(to).unwrap();
+
+    FileExtImpl::copy_file_with_callbacks_starting_from_byte(vec![from], vec![to], 10, Some(1000), |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(&content[10..], FileExtImpl::read_file(to).unwrap().as_slice());
+    FileExtImpl::delete_file(to).unwrap();
+
+    FileExtImpl::delete_file(from).unwrap();
+}
+
+#[test]
+fn copy_range_ending_at_last_byte() {
+    let from = "test/copy_range_ending_at_last_byte-source.bin";
+    let to = "test/copy_range_ending_at_last_byte-destination.bin";
+    let content = create_copy_source(from, 100);
+
+    for block_size in [1, 3, 7, 50, 96, 100] {
+        let mut block_list = vec![];
+        FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(
+            vec![from],
+            vec![to],
+            4,
+            99,
+            Some(block_size),
+            |start, end, _total| block_list.push((start, end)),
+            |_, _, _| false,
+        ).unwrap();
+
+        assert_eq!(&content[4..], FileExtImpl::read_file(to).unwrap().as_slice(), "block size {}", block_size);
+        assert_eq!(4, block_list.first().unwrap().0);
+        assert_eq!(99, block_list.last().unwrap().1);
+        FileExtImpl::delete_file(to).unwrap();
+    }
+
+    FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(vec![from], vec![to], 10, 19, Some(3), |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(&content[10..=19], FileExtImpl::read_file(to).unwrap().as_slice());
+    FileExtImpl::delete_file(to).unwrap();
+
+    let boxed_copy = FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(vec![from], vec![to], 4, 100, None, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::InvalidInput, boxed_copy.err().unwrap().kind());
+
+    let boxed_copy = FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(vec![from], vec![to], 20, 10, None, |_, _, _| {}, |_, _, _| false);
+    assert_eq!(ErrorKind::InvalidInput, boxed_copy.err().unwrap().kind());
+
+    let range = FileRange::new(90, 100).unwrap();
+    FileExtImpl::copy_file_range_with_callbacks(vec![from], vec![to], range, Some(4), |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(&content[90..100], FileExtImpl::read_file(to).unwrap().as_slice());
+    FileExtImpl::delete_file(to).unwrap();
+
+    FileExtImpl::delete_file(from).unwrap();
+}
@@ -0,0 +1,332 @@
+use std::path::{Path, PathBuf};
+use crate::byte_range::{ByteRange, ByteRangeContent};
+use crate::copy_directory_options::{CopyDirectoryOptions, CopyDirectoryStatus};
+use crate::delete_directory_options::{DeleteDirectoryOptions, DeleteDirectoryReport};
+use crate::directory_entry::{DirectoryEntry, ListDirectoryOptions};
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::directory_walker::DirectoryWalker;
+use crate::file_ext_error::FileExtError;
+use crate::file_ext_impl::FileExtImpl;
+use crate::file_range::FileRange;
+use crate::file_reader::FileReader;
+use crate::FileExt;
+use crate::glob_walker::GlobWalker;
+use crate::hard_link_ext_impl::HardLinkExtImpl;
+use crate::path_ext_impl::PathExtImpl;
+use crate::resumable_copy_impl::{ResumableCopyImpl, ResumableCopyStatus};
+use crate::symlink_chain::SymlinkChain;
+use crate::symlink_ext_impl::SymlinkExtImpl;
+use crate::symlink_options::SymlinkOptions;
+use crate::write_mode::WriteMode;
+
+#[cfg(test)]
+mod tests;
+
+/// Counterpart of `FileExt` taking `AsRef<Path>` instead of `&str`.
+///
+/// Paths are passed to the operating system as is, so file names that are not valid UTF-8
+/// (common on Linux for uploaded files) can be read, written, listed, copied, linked and deleted.
+/// Returned paths are `PathBuf` and round-trip losslessly, `DirectoryEntry::os_path` and
+/// `SymlinkChain::os_hop_list` do the same for entries and symlink chains.
+/// Path in a `FileExtError` is still a `String`, invalid sequences are replaced by U+FFFD there.
+///
+/// Functions which only build or split path strings, such as `build_path` or `normalize_path`,
+/// are left out, `Path` and `PathBuf` provide them.
+pub struct FileExtPath;
+
+impl FileExtPath {
+
+    /// Returns file content
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use file_ext::FileExtPath;
+    /// #[test]
+    /// fn read_file() {
+    ///     let content = FileExtPath::read_file(Path::new("test").join("index.html")).unwrap();
+    ///     assert!(!content.is_empty());
+    /// }
+    /// ```
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileExtError> {
+        FileExtImpl::read_file(path)
+    }
+
+    /// Returns portion of a file from byte `start` up to byte `end` inclusive
+    pub fn read_file_partially<P: AsRef<Path>>(path: P, start: u64, end: u64) -> Result<Vec<u8>, FileExtError> {
+        FileExtImpl::read_file_partially(path, start, end)
+    }
+
+    /// Returns several portions of a file, see `FileExt::read_file_ranges`
+    pub fn read_file_ranges<P: AsRef<Path>>(path: P, range_list: &[ByteRange]) -> Result<Vec<ByteRangeContent>, FileExtError> {
+        FileExtImpl::read_file_ranges(path, range_list)
+    }
+
+    /// Same as `read_file_ranges`, returns result as a `multipart/byteranges` body separated by the given boundary
+    pub fn read_file_ranges_as_multipart<P: AsRef<Path>>(path: P, range_list: &[ByteRange], content_type: &str, boundary: &str) -> Result<Vec<u8>, FileExtError> {
+        FileExtImpl::read_file_ranges_as_multipart(path, range_list, content_type, boundary)
+    }
+
+    /// Opens file for sequential reading in chunks, see `FileExt::open_reader`
+    pub fn open_reader<P: AsRef<Path>>(path: P, chunk_size: Option<usize>) -> Result<FileReader, FileExtError> {
+        FileExtImpl::open_reader(path, chunk_size)
+    }
+
+    /// Appends content to the end of the file
+    pub fn write_file<P: AsRef<Path>>(path: P, file_content: &[u8]) -> Result<(), FileExtError> {
+        FileExtImpl::write_file(path, file_content)
+    }
+
+    /// Writes content to the file according to the given `WriteMode`
+    pub fn write_file_with_mode<P: AsRef<Path>>(path: P, file_content: &[u8], mode: WriteMode) -> Result<(), FileExtError> {
+        FileExtImpl::write_file_with_mode(path, file_content, mode)
+    }
+
+    /// Replaces file content via temp file and rename, see `FileExt::write_file_atomically`
+    pub fn write_file_atomically<P: AsRef<Path>>(path: P, file_content: &[u8]) -> Result<(), FileExtError> {
+        FileExtImpl::write_file_atomically(path, file_content)
+    }
+
+    /// Reads the file, if it does not exist creates it with the given content
+    pub fn read_or_create_and_write<P: AsRef<Path>>(path: P, content: &[u8]) -> Result<Vec<u8>, FileExtError> {
+        FileExtImpl::read_or_create_and_write(path, content)
+    }
+
+    /// Creates an empty file, existing file is truncated
+    pub fn create_file<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        FileExtImpl::create_file(path)
+    }
+
+    /// Checks if there is a file at the given path
+    pub fn does_file_exist<P: AsRef<Path>>(path: P) -> bool {
+        FileExtImpl::does_file_exist(path)
+    }
+
+    /// Removes the file
+    pub fn delete_file<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        FileExtImpl::delete_file(path)
+    }
+
+    /// Returns file modification timestamp as nanoseconds in Unix epoch
+    pub fn file_modified_utc<P: AsRef<Path>>(path: P) -> Result<u128, FileExtError> {
+        FileExtImpl::file_modified_utc(path)
+    }
+
+    /// Returns file length in bytes
+    pub fn file_length<P: AsRef<Path>>(path: P) -> Result<u64, FileExtError> {
+        FileExtImpl::file_length_path(path)
+    }
+
+    /// Appends content of the source file to the destination, destination is created if it does not exist
+    pub fn copy_file<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<(), FileExtError> {
+        FileExtImpl::copy_path(from, to)
+    }
+
+    /// Copies file block by block, see `FileExt::copy_file_with_callbacks`
+    pub fn copy_file_with_callbacks
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        block_size: Option<u64>,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<(), FileExtError> {
+        FileExtImpl::copy_path_with_callbacks(from, to, block_size, progress_callback, cancel_callback)
+    }
+
+    /// Copies bytes of the source in the given range, see `FileExt::copy_file_range_with_callbacks`
+    pub fn copy_file_range_with_callbacks
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        range: FileRange,
+        block_size: Option<u64>,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<(), FileExtError> {
+        FileExtImpl::copy_path_range_with_callbacks(from, to, range, block_size, progress_callback, cancel_callback)
+    }
+
+    /// Copies file and records progress in a checkpoint next to the destination, see `FileExt::copy_file_resumable`
+    pub fn copy_file_resumable
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        block_size: Option<u64>,
+        overwrite: bool,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<ResumableCopyStatus, FileExtError> {
+        ResumableCopyImpl::copy_path_resumable(from, to, block_size, overwrite, progress_callback, cancel_callback)
+    }
+
+    /// Checks if there is a directory at the given path
+    pub fn does_directory_exist<P: AsRef<Path>>(path: P) -> bool {
+        DirectoryExtImpl::does_directory_exist(path)
+    }
+
+    /// Creates the directory with all missing parents, fails if something already exists at the path
+    pub fn create_directory<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        DirectoryExtImpl::create_directory(path)
+    }
+
+    /// Creates the directory with all missing parents (`mkdir -p`), see `FileExt::ensure_directory`
+    pub fn ensure_directory<P: AsRef<Path>>(path: P, mode: Option<u32>) -> Result<Vec<PathBuf>, FileExtError> {
+        DirectoryExtImpl::ensure_directory_path(path, mode)
+    }
+
+    /// Removes the directory with all of its content, symlinks are never followed
+    pub fn delete_directory<P: AsRef<Path>>(path: P) -> Result<(), FileExtError> {
+        DirectoryExtImpl::delete_directory(path)
+    }
+
+    /// Removes the directory with all of its content, see `FileExt::delete_directory_with_options`
+    pub fn delete_directory_with_options<P: AsRef<Path>>(path: P, options: &DeleteDirectoryOptions) -> Result<DeleteDirectoryReport, FileExtError> {
+        DirectoryExtImpl::delete_directory_with_options(path, options)
+    }
+
+    /// Returns entries of the directory sorted by name, see `FileExt::list_directory`
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use file_ext::FileExtPath;
+    /// #[test]
+    /// fn list_directory() {
+    ///     let entry_list = FileExtPath::list_directory("test").unwrap();
+    ///     let entry = entry_list.iter().find(|entry| entry.name == "index.html").unwrap();
+    ///     assert_eq!(Path::new("test").join("index.html"), entry.os_path);
+    /// }
+    /// ```
+    pub fn list_directory<P: AsRef<Path>>(path: P) -> Result<Vec<DirectoryEntry>, FileExtError> {
+        DirectoryExtImpl::list_directory(path, &ListDirectoryOptions::default())
+    }
+
+    /// Same as `list_directory`, entries are sorted and symlinks are followed according to the options
+    pub fn list_directory_with_options<P: AsRef<Path>>(path: P, options: &ListDirectoryOptions) -> Result<Vec<DirectoryEntry>, FileExtError> {
+        DirectoryExtImpl::list_directory(path, options)
+    }
+
+    /// Recursively walks the directory tree, see `FileExt::walk_directory`
+    pub fn walk_directory<P: AsRef<Path>>(path: P, options: &ListDirectoryOptions) -> Result<DirectoryWalker, FileExtError> {
+        DirectoryExtImpl::walk_directory(path, options)
+    }
+
+    /// Lazily finds entries matching the glob pattern, see `FileExt::glob`. Pattern is a string,
+    /// `os_path` of the found entries keeps file names which are not valid UTF-8
+    pub fn glob(pattern: &str) -> Result<GlobWalker, FileExtError> {
+        FileExt::glob(pattern)
+    }
+
+    /// Copies directory with all of its content, see `FileExt::copy_directory`
+    pub fn copy_directory
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        options: &CopyDirectoryOptions,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<CopyDirectoryStatus, FileExtError> {
+        DirectoryExtImpl::copy_directory(from, to, options, progress_callback, cancel_callback)
+    }
+
+    /// Checks if there is a symlink at the given path
+    pub fn does_symlink_exist<P: AsRef<Path>>(path: P) -> bool {
+        SymlinkExtImpl::does_symlink_exist(path)
+    }
+
+    /// Checks if the path is a symlink, fails if nothing exists at the path
+    pub fn is_symlink<P: AsRef<Path>>(path: P) -> Result<bool, FileExtError> {
+        SymlinkExtImpl::is_symlink(path)
+    }
+
+    /// Checks if the path is a symlink whose target does not exist
+    pub fn is_dangling<P: AsRef<Path>>(path: P) -> Result<bool, FileExtError> {
+        SymlinkExtImpl::is_dangling(path)
+    }
+
+    /// Returns path the symlink points to, exactly as it is stored in the symlink
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use file_ext::FileExtPath;
+    /// #[test]
+    /// fn link_points_to() {
+    ///     let points_to = FileExtPath::symlink_points_to(Path::new("test").join("index_rewrite")).unwrap();
+    ///     assert_eq!(Path::new("index.html"), points_to);
+    /// }
+    /// ```
+    pub fn symlink_points_to<P: AsRef<Path>>(path: P) -> Result<PathBuf, FileExtError> {
+        SymlinkExtImpl::symlink_points_to_path(path)
+    }
+
+    /// Creates symlink `symlink_name` in `symlink_path` pointing to `symlink_points_to`, see `FileExt::create_symlink`
+    #[cfg(any(target_family = "unix", target_family = "windows"))]
+    pub fn create_symlink<P: AsRef<Path>, Q: AsRef<Path>, R: AsRef<Path>>(symlink_path: P, symlink_name: Q, symlink_points_to: R) -> Result<(), FileExtError> {
+        SymlinkExtImpl::create_symlink(symlink_path, symlink_name, symlink_points_to)
+    }
+
+    /// Same as `create_symlink`, see `FileExt::create_symlink_with_options`
+    #[cfg(any(target_family = "unix", target_family = "windows"))]
+    pub fn create_symlink_with_options<P: AsRef<Path>, Q: AsRef<Path>, R: AsRef<Path>>(symlink_path: P, symlink_name: Q, symlink_points_to: R, options: &SymlinkOptions) -> Result<(), FileExtError> {
+        SymlinkExtImpl::create_symlink_with_options(symlink_path, symlink_name, symlink_points_to, options)
+    }
+
+    /// Points existing symlink to the new target atomically and returns the previous target, see `FileExt::replace_symlink`
+    #[cfg(target_family = "unix")]
+    pub fn replace_symlink<P: AsRef<Path>, Q: AsRef<Path>>(link: P, new_target: Q) -> Result<PathBuf, FileExtError> {
+        SymlinkExtImpl::replace_symlink_path(link, new_target)
+    }
+
+    /// Returns paths of dangling symlinks in the directory tree, symlinks to directories are not followed
+    pub fn find_broken_symlinks<P: AsRef<Path>>(directory: P) -> Result<Vec<PathBuf>, FileExtError> {
+        SymlinkExtImpl::find_broken_symlink_paths(directory)
+    }
+
+    /// Follows symlink through all of its hops, see `FileExt::resolve_symlink_chain`
+    pub fn resolve_symlink_chain<P: AsRef<Path>>(path: P) -> Result<SymlinkChain, FileExtError> {
+        SymlinkExtImpl::resolve_symlink_chain(path)
+    }
+
+    /// Creates hard link `new_path` to the file `existing_path`, see `FileExt::create_hard_link`
+    pub fn create_hard_link<P: AsRef<Path>, Q: AsRef<Path>>(existing_path: P, new_path: Q) -> Result<(), FileExtError> {
+        HardLinkExtImpl::create_hard_link(existing_path, new_path)
+    }
+
+    /// Returns number of hard links to the file, available on Unix only
+    #[cfg(target_family = "unix")]
+    pub fn hard_link_count<P: AsRef<Path>>(path: P) -> Result<u64, FileExtError> {
+        HardLinkExtImpl::hard_link_count(path)
+    }
+
+    /// Checks if both paths refer to the same file, symlinks are followed. Available on Unix only
+    #[cfg(target_family = "unix")]
+    pub fn are_same_file<P: AsRef<Path>, Q: AsRef<Path>>(path: P, other_path: Q) -> Result<bool, FileExtError> {
+        HardLinkExtImpl::are_same_file(path, other_path)
+    }
+
+    /// Joins untrusted relative path with the root and makes sure the result stays within the root,
+    /// see `FileExt::resolve_within`. Untrusted path usually comes from a request, so it is a string
+    pub fn resolve_within<P: AsRef<Path>>(root: P, untrusted_path: &str, resolve_symlinks: bool) -> Result<PathBuf, FileExtError> {
+        PathExtImpl::resolve_within_path(root, untrusted_path, resolve_symlinks)
+    }
+
+    /// Returns absolute path to the working directory
+    pub fn working_directory() -> Result<PathBuf, FileExtError> {
+        PathExtImpl::working_directory_path()
+    }
+
+    /// Returns temporary folder of the platform, see `FileExt::get_temp_folder_path`
+    pub fn get_temp_folder_path() -> PathBuf {
+        PathExtImpl::temp_folder_path()
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::{Path, PathBuf};
+use crate::byte_range::ByteRange;
+use crate::copy_directory_options::{CopyDirectoryOptions, CopyDirectoryStatus};
+use crate::directory_entry::ListDirectoryOptions;
+use crate::file_ext_error::FileExtError;
+use crate::file_ext_path::FileExtPath;
+use crate::FileExt;
+
+#[test]
+fn same_result_as_str_functions() {
+    let path = Path::new("test").join("index.html");
+    let expected = FileExt::read_file("test/index.html").unwrap();
+    assert_eq!(expected, FileExtPath::read_file(&path).unwrap());
+    assert_eq!(expected[4..11].to_vec(), FileExtPath::read_file_partially(&path, 4, 10).unwrap());
+
+    let working_directory = FileExtPath::working_directory().unwrap();
+    assert_eq!(PathBuf::from(FileExt::working_directory().unwrap()), working_directory);
+}
+
+#[test]
+fn write_copy_and_delete_directory() {
+    let directory = Path::new("file_ext_path_write_copy_and_delete_directory");
+    let nested = directory.join("nested");
+
+    let created_list = FileExtPath::ensure_directory(&nested, None).unwrap();
+    assert_eq!(vec![directory.to_path_buf(), nested.clone()], created_list);
+
+    let from = nested.join("from.txt");
+    let to = nested.join("to.txt");
+    FileExtPath::create_file(&from).unwrap();
+    FileExtPath::write_file(&from, b"content").unwrap();
+    FileExtPath::copy_file(&from, &to).unwrap();
+    assert_eq!(b"content".to_vec(), FileExtPath::read_file(&to).unwrap());
+
+    FileExtPath::write_file_atomically(&to, b"replaced").unwrap();
+    assert_eq!(b"replaced".to_vec(), FileExtPath::read_file(&to).unwrap());
+
+    FileExtPath::delete_directory(directory).unwrap();
+    assert!(!FileExtPath::does_directory_exist(directory));
+}
+
+#[test]
+fn rejected_path() {
+    let error = FileExtPath::read_file(Path::new("directory/index;.html")).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+    assert_eq!("directory/index;.html", error.path());
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn non_utf8_file_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let directory = Path::new("file_ext_path_non_utf8_file_name");
+    // latin-1 encoded "café.txt", 0xE9 is not valid UTF-8 on its own
+    let file_name = OsStr::from_bytes(b"caf\xe9.txt");
+    let path = directory.join(file_name);
+    assert!(path.to_str().is_none());
+
+    FileExtPath::create_directory(directory).unwrap();
+    FileExtPath::create_file(&path).unwrap();
+    FileExtPath::write_file(&path, b"upload").unwrap();
+    assert!(FileExtPath::does_file_exist(&path));
+    assert_eq!(b"upload".to_vec(), FileExtPath::read_file(&path).unwrap());
+    assert!(FileExtPath::file_modified_utc(&path).is_ok());
+
+    let error = FileExtPath::read_file(directory.join(OsStr::from_bytes(b"missing\xe9"))).err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+    assert!(error.path().ends_with("missing\u{FFFD}"));
+
+    let symlink = directory.join("link");
+    std::os::unix::fs::symlink(file_name, &symlink).unwrap();
+    assert!(FileExtPath::is_symlink(&symlink).unwrap());
+    assert_eq!(Path::new(file_name), FileExtPath::symlink_points_to(&symlink).unwrap());
+
+    // String can not hold the target, so &str based function reports it instead of mangling
+    let error = FileExt::symlink_points_to(symlink.to_str().unwrap()).err().unwrap();
+    assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+
+    FileExtPath::delete_file(&path).unwrap();
+    assert!(!FileExtPath::does_file_exist(&path));
+
+    FileExtPath::delete_directory(directory).unwrap();
+}
+
+#[test]
+fn read_ranges_and_file_length() {
+    let path = Path::new("test").join("index.html");
+    let length = FileExtPath::file_length(&path).unwrap();
+    assert_eq!(FileExt::file_length(vec!["test", "index.html"]).unwrap(), length);
+
+    let range_list = vec![ByteRange::Inclusive { start: 4, end: 10 }, ByteRange::Suffix { length: 2 }];
+    let content_list = FileExtPath::read_file_ranges(&path, &range_list).unwrap();
+    assert_eq!(FileExt::read_file_ranges("test/index.html", &range_list).unwrap(), content_list);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn non_utf8_directory_operations() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let directory = Path::new("file_ext_path_non_utf8_directory_operations");
+    let source = directory.join("source");
+    let nested = source.join(OsStr::from_bytes(b"r\xe9sum\xe9"));
+    let file_name = OsStr::from_bytes(b"caf\xe9.txt");
+    let path = nested.join(file_name);
+
+    FileExtPath::ensure_directory(&nested, None).unwrap();
+    FileExtPath::create_file(&path).unwrap();
+    FileExtPath::write_file(&path, b"upload").unwrap();
+
+    let entry_list = FileExtPath::list_directory(&source).unwrap();
+    assert_eq!(1, entry_list.len());
+    assert_eq!(nested, entry_list[0].os_path);
+
+    let walked_list : Vec<PathBuf> = FileExtPath::walk_directory(&source, &ListDirectoryOptions::default()).unwrap()
+        .map(|entry| entry.unwrap().os_path)
+        .collect();
+    assert_eq!(vec![nested.clone(), path.clone()], walked_list);
+
+    let copy = directory.join("copy");
+    let status = FileExtPath::copy_directory(&source, &copy, &CopyDirectoryOptions::default(), |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(CopyDirectoryStatus::Completed { bytes_copied: 6 }, status);
+    let copied_path = copy.join(OsStr::from_bytes(b"r\xe9sum\xe9")).join(file_name);
+    assert_eq!(b"upload".to_vec(), FileExtPath::read_file(&copied_path).unwrap());
+
+    let absolute_path = FileExtPath::working_directory().unwrap().join(&path);
+    FileExtPath::create_symlink(directory, "link", &absolute_path).unwrap();
+    let chain = FileExtPath::resolve_symlink_chain(directory.join("link")).unwrap();
+    assert_eq!(absolute_path, chain.os_target);
+
+    let hard_link = nested.join(OsStr::from_bytes(b"hard\xe9"));
+    FileExtPath::create_hard_link(&path, &hard_link).unwrap();
+    assert!(FileExtPath::are_same_file(&path, &hard_link).unwrap());
+    assert_eq!(2, FileExtPath::hard_link_count(&path).unwrap());
+
+    FileExtPath::delete_directory(directory).unwrap();
+}
@@ -0,0 +1,94 @@
+use std::io::ErrorKind;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::symbol::SYMBOL;
+
+#[cfg(test)]
+mod tests;
+
+/// Half-open range of bytes in a file: `start` is included, `end` is excluded.
+/// Empty range has `start == end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileRange {
+    start: u64,
+    end: u64,
+}
+
+impl FileRange {
+    pub fn new(start: u64, end: u64) -> Result<FileRange, FileExtError> {
+        if start > end {
+            let message = format!("range start {} is greater than range end {}", start, end);
+            return Err(FileExtError::from_kind(Operation::Copy, SYMBOL.empty_string, ErrorKind::InvalidInput, message))
+        }
+        Ok(FileRange { start, end })
+    }
+
+    /// Range covering the whole file of the given length
+    pub fn whole(file_length: u64) -> FileRange {
+        FileRange { start: 0, end: file_length }
+    }
+
+    /// Range described with inclusive ending byte, as in `copy_file_with_callbacks_starting_from_byte_and_ending_at_byte`
+    pub fn inclusive(start: u64, ending_byte: u64) -> Result<FileRange, FileExtError> {
+        if ending_byte == u64::MAX {
+            let message = format!("ending byte {} is too big", ending_byte);
+            return Err(FileExtError::from_kind(Operation::Copy, SYMBOL.empty_string, ErrorKind::InvalidInput, message))
+        }
+        FileRange::new(start, ending_byte + 1)
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Checks that range fits into a file of the given length
+    pub fn check_within(&self, file_length: u64) -> Result<(), FileExtError> {
+        if self.end > file_length {
+            let message = format!("file length is {}, range is {}..{}", file_length, self.start, self.end);
+            return Err(FileExtError::from_kind(Operation::Copy, SYMBOL.empty_string, ErrorKind::InvalidInput, message))
+        }
+        Ok(())
+    }
+
+    /// Splits range into consecutive blocks of `block_size` bytes, the last block may be shorter.
+    /// Empty range produces no blocks.
+    pub fn blocks(&self, block_size: u64) -> FileRangeBlocks {
+        FileRangeBlocks {
+            position: self.start,
+            end: self.end,
+            block_size: block_size.max(1),
+        }
+    }
+}
+
+pub struct FileRangeBlocks {
+    position: u64,
+    end: u64,
+    block_size: u64,
+}
+
+impl Iterator for FileRangeBlocks {
+    type Item = FileRange;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.end {
+            return None
+        }
+
+        let block_end = self.end.min(self.position.saturating_add(self.block_size));
+        let block = FileRange { start: self.position, end: block_end };
+        self.position = block_end;
+        Some(block)
+    }
+}
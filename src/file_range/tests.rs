@@ -0,0 +1,58 @@
+use std::io::ErrorKind;
+use crate::file_range::FileRange;
+
+#[test]
+fn new() {
+    let range = FileRange::new(4, 10).unwrap();
+    assert_eq!(4, range.start());
+    assert_eq!(10, range.end());
+    assert_eq!(6, range.len());
+    assert!(!range.is_empty());
+
+    let empty_range = FileRange::new(7, 7).unwrap();
+    assert_eq!(0, empty_range.len());
+    assert!(empty_range.is_empty());
+
+    let boxed_range = FileRange::new(10, 4);
+    assert_eq!(ErrorKind::InvalidInput, boxed_range.err().unwrap().kind());
+}
+
+#[test]
+fn inclusive() {
+    assert_eq!(FileRange::new(4, 11).unwrap(), FileRange::inclusive(4, 10).unwrap());
+    assert_eq!(FileRange::new(0, 1).unwrap(), FileRange::inclusive(0, 0).unwrap());
+    assert!(FileRange::inclusive(5, 3).is_err());
+    assert!(FileRange::inclusive(0, u64::MAX).is_err());
+}
+
+#[test]
+fn check_within() {
+    assert!(FileRange::whole(10).check_within(10).is_ok());
+    assert!(FileRange::new(10, 10).unwrap().check_within(10).is_ok());
+    assert!(FileRange::whole(0).check_within(0).is_ok());
+    assert!(FileRange::new(5, 11).unwrap().check_within(10).is_err());
+}
+
+#[test]
+fn blocks() {
+    let block_list : Vec<(u64, u64)> = FileRange::new(0, 10).unwrap()
+        .blocks(4)
+        .map(|block| (block.start(), block.end()))
+        .collect();
+    assert_eq!(vec![(0, 4), (4, 8), (8, 10)], block_list);
+
+    let block_list : Vec<(u64, u64)> = FileRange::new(3, 9).unwrap()
+        .blocks(3)
+        .map(|block| (block.start(), block.end()))
+        .collect();
+    assert_eq!(vec![(3, 6), (6, 9)], block_list);
+
+    let block_list : Vec<FileRange> = FileRange::new(0, 1).unwrap().blocks(100).collect();
+    assert_eq!(vec![FileRange::new(0, 1).unwrap()], block_list);
+
+    assert_eq!(0, FileRange::whole(0).blocks(100).count());
+    assert_eq!(0, FileRange::new(5, 5).unwrap().blocks(100).count());
+
+    // zero block size is treated as one byte blocks
+    assert_eq!(3, FileRange::whole(3).blocks(0).count());
+}
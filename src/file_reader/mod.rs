@@ -0,0 +1,96 @@
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use crate::file_ext_error::{FileExtError, Operation};
+
+#[cfg(test)]
+mod tests;
+
+/// Reads file in chunks of fixed size, so the whole file is never loaded into memory.
+///
+/// Can be used as `std::io::Read` (for example with `std::io::copy`) or as an iterator
+/// over chunks. Each chunk is at most `chunk_size` bytes long, only the last one may be shorter.
+pub struct FileReader {
+    path: String,
+    file: File,
+    chunk_size: usize,
+    finished: bool,
+}
+
+impl FileReader {
+    pub const DEFAULT_CHUNK_SIZE: usize = 102400;
+
+    pub(crate) fn new(path: &str, file: File, chunk_size: usize) -> FileReader {
+        FileReader {
+            path: path.to_string(),
+            file,
+            chunk_size,
+            finished: false,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Reads next chunk into the given buffer, reusing its allocation. Returns number of bytes read, 0 means end of file.
+    pub fn read_chunk(&mut self, buffer: &mut Vec<u8>) -> Result<usize, FileExtError> {
+        buffer.clear();
+        if self.finished {
+            return Ok(0)
+        }
+
+        buffer.resize(self.chunk_size, 0);
+        let mut filled = 0;
+        while filled < self.chunk_size {
+            let boxed_read = self.file.read(&mut buffer[filled..]);
+            if boxed_read.is_err() {
+                let error = boxed_read.err().unwrap();
+                if error.kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+                buffer.clear();
+                self.finished = true;
+                return Err(FileExtError::from_io(Operation::Read, &self.path, error))
+            }
+
+            let bytes_read = boxed_read.unwrap();
+            if bytes_read == 0 {
+                self.finished = true;
+                break;
+            }
+            filled += bytes_read;
+        }
+
+        buffer.truncate(filled);
+        Ok(filled)
+    }
+}
+
+impl Read for FileReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl Iterator for FileReader {
+    type Item = Result<Vec<u8>, FileExtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        let boxed_read = self.read_chunk(&mut chunk);
+        if boxed_read.is_err() {
+            let error = boxed_read.err().unwrap();
+            return Some(Err(error))
+        }
+
+        let bytes_read = boxed_read.unwrap();
+        if bytes_read == 0 {
+            return None
+        }
+        Some(Ok(chunk))
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{ErrorKind, Read};
+use crate::file_ext_error::FileExtError;
+use crate::file_ext_impl::FileExtImpl;
+use crate::file_reader::FileReader;
+
+#[test]
+fn read_in_chunks() {
+    let path = "test/index.html";
+    let expected_content = FileExtImpl::read_file(path).unwrap();
+
+    let reader = FileExtImpl::open_reader(path, Some(10)).unwrap();
+    assert_eq!(10, reader.chunk_size());
+    assert_eq!(path, reader.path());
+
+    let chunks : Vec<Vec<u8>> = reader.map(|boxed_chunk| boxed_chunk.unwrap()).collect();
+    let last = chunks.len() - 1;
+    for (index, chunk) in chunks.iter().enumerate() {
+        if index != last {
+            assert_eq!(10, chunk.len());
+        }
+        assert!(!chunk.is_empty());
+    }
+
+    assert_eq!(expected_content, chunks.concat());
+}
+
+#[test]
+fn read_chunk_reuses_buffer() {
+    let path = "test/index.html";
+    let expected_content = FileExtImpl::read_file(path).unwrap();
+
+    let mut reader = FileExtImpl::open_reader(path, Some(1000)).unwrap();
+    let mut buffer = vec![];
+
+    let bytes_read = reader.read_chunk(&mut buffer).unwrap();
+    assert_eq!(expected_content.len(), bytes_read);
+    assert_eq!(expected_content, buffer);
+
+    let bytes_read = reader.read_chunk(&mut buffer).unwrap();
+    assert_eq!(0, bytes_read);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn default_chunk_size() {
+    let reader = FileExtImpl::open_reader("test/index.html", None).unwrap();
+    assert_eq!(FileReader::DEFAULT_CHUNK_SIZE, reader.chunk_size());
+}
+
+#[test]
+fn read_trait() {
+    let path = "test/index.html";
+    let expected_content = FileExtImpl::read_file(path).unwrap();
+
+    let mut reader = FileExtImpl::open_reader(path, None).unwrap();
+    let mut content = vec![];
+    std::io::copy(&mut reader, &mut content).unwrap();
+    assert_eq!(expected_content, content);
+
+    let mut rest = vec![];
+    reader.read_to_end(&mut rest).unwrap();
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn empty_file() {
+    let path = "test/file_reader-empty.txt";
+    FileExtImpl::create_file(path).unwrap();
+
+    let mut reader = FileExtImpl::open_reader(path, None).unwrap();
+    assert!(reader.next().is_none());
+
+    FileExtImpl::delete_file(path).unwrap();
+}
+
+#[test]
+fn errors() {
+    let boxed_reader = FileExtImpl::open_reader("test/non_existing_file", None);
+    assert_eq!(ErrorKind::NotFound, boxed_reader.err().unwrap().kind());
+
+    let boxed_reader = FileExtImpl::open_reader("test/index.html", Some(0));
+    assert_eq!(ErrorKind::InvalidInput, boxed_reader.err().unwrap().kind());
+
+    let boxed_reader = FileExtImpl::open_reader("test/index.html;", None);
+    assert!(matches!(boxed_reader.err().unwrap(), FileExtError::InvalidPath { .. }));
+}
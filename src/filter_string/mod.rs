@@ -1,20 +1,23 @@
-use crate::symbol::SYMBOL;
+use std::path::Path;
+use crate::path_policy::PathPolicy;
 
 pub struct FilterString;
 
 impl FilterString {
+    /// Checks path against the current `PathPolicy`, returns rejection reason on failure
     pub fn is_valid_input_string(path: &str) -> Result<(), String> {
-        let path = path.replace(|x : char | x.is_ascii_control(), SYMBOL.empty_string).trim().to_string();
-
-        if path.contains(SYMBOL.whitespace) ||
-            path.contains(SYMBOL.single_quote) ||
-            path.contains(SYMBOL.quotation_mark) ||
-            path.contains(SYMBOL.ampersand) ||
-            path.contains(SYMBOL.pipe) ||
-            path.contains(SYMBOL.semicolon) {
-            return Err(format!("Path contains not allowed characters: whitespace, single quote, quotation mark, ampersand, pipe, semicolon. Path: {}",path))
+        let boxed_check = PathPolicy::current().check(path);
+        if boxed_check.is_err() {
+            let violation = boxed_check.err().unwrap();
+            return Err(format!("Path is not allowed: {}. Path: {}", violation, path))
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Same as `is_valid_input_string`, path that is not valid UTF-8 is checked by its lossy form,
+    /// invalid sequences are replaced by U+FFFD and never match a rejected character
+    pub fn is_valid_input_path(path: &Path) -> Result<(), String> {
+        FilterString::is_valid_input_string(&path.to_string_lossy())
+    }
+}
@@ -0,0 +1,372 @@
+use std::io::ErrorKind;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::path_ext_impl::PathExtImpl;
+
+#[cfg(test)]
+mod tests;
+
+/// Compiled glob pattern, can be matched against path strings or used to find files via `FileExt::glob`.
+///
+/// Supported syntax:
+/// - `*` any number of characters within one path segment
+/// - `?` exactly one character within one path segment
+/// - `**` as a whole segment, any number of path segments, including none
+/// - `[abc]`, `[a-z]` character classes, `[!abc]` or `[^abc]` negated classes
+/// - `{css,scss}` alternatives, may be nested
+/// - leading `!` negates the whole pattern
+///
+/// Segments are separated by `/`, on Windows `\` is accepted as well.
+/// Leading dot in file names is not special, `*` matches `.hidden` too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobPattern {
+    pattern: String,
+    negated: bool,
+    // brace alternatives expanded, each one is a list of path segments
+    alternative_list: Vec<Vec<Segment>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    AnyDepth,
+    Literal(String),
+    Wildcard(Vec<Token>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Char(char),
+    AnyChar,
+    AnyString,
+    Class { negated: bool, range_list: Vec<(char, char)> },
+}
+
+impl GlobPattern {
+    pub fn new(pattern: &str) -> Result<GlobPattern, FileExtError> {
+        let (negated, body) = match pattern.strip_prefix('!') {
+            Some(body) => (true, body),
+            None => (false, pattern),
+        };
+
+        let boxed_expanded = GlobPattern::expand_braces(body);
+        if boxed_expanded.is_err() {
+            let message = boxed_expanded.err().unwrap();
+            return Err(FileExtError::from_kind(Operation::Glob, pattern, ErrorKind::InvalidInput, message))
+        }
+
+        let mut alternative_list = vec![];
+        for alternative in boxed_expanded.unwrap() {
+            let mut segment_list = vec![];
+            for part in GlobPattern::split_path(&alternative) {
+                let boxed_segment = GlobPattern::parse_segment(part);
+                if boxed_segment.is_err() {
+                    let message = boxed_segment.err().unwrap();
+                    return Err(FileExtError::from_kind(Operation::Glob, pattern, ErrorKind::InvalidInput, message))
+                }
+                segment_list.push(boxed_segment.unwrap());
+            }
+            alternative_list.push(segment_list);
+        }
+
+        Ok(GlobPattern {
+            pattern: pattern.to_string(),
+            negated,
+            alternative_list,
+        })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Checks if the path string matches the pattern. Path is not required to exist
+    pub fn is_match(&self, path: &str) -> bool {
+        let part_list = GlobPattern::split_path(path);
+        let is_match = self.alternative_list
+            .iter()
+            .any(|segment_list| GlobPattern::match_segments(segment_list, &part_list));
+        is_match != self.negated
+    }
+
+    /// Directories which have to be walked to find all matches, with the maximum depth
+    /// the walk has to go below each of them (None if pattern contains `**`).
+    /// Base is the literal path prefix of the pattern, `.` if there is none
+    pub(crate) fn base_list(&self) -> Vec<(String, Option<usize>)> {
+        if self.negated {
+            return vec![(".".to_string(), None)]
+        }
+
+        let mut base_list: Vec<(String, Option<usize>)> = vec![];
+        for segment_list in self.alternative_list.iter() {
+            let mut literal_list = vec![];
+            for segment in segment_list.iter().take(segment_list.len().saturating_sub(1)) {
+                match segment {
+                    Segment::Literal(literal) => literal_list.push(literal.as_str()),
+                    _ => break,
+                }
+            }
+
+            let remaining = &segment_list[literal_list.len()..];
+            let depth = if remaining.contains(&Segment::AnyDepth) {
+                None
+            } else {
+                Some(remaining.len())
+            };
+
+            let base = if literal_list.is_empty() {
+                ".".to_string()
+            } else if literal_list == [""] {
+                // absolute pattern, first segment is empty
+                PathExtImpl::get_path_separator()
+            } else {
+                PathExtImpl::build_path(&literal_list)
+            };
+
+            if !base_list.contains(&(base.clone(), depth)) {
+                base_list.push((base, depth));
+            }
+        }
+        base_list
+    }
+
+    fn split_path(path: &str) -> Vec<&str> {
+        #[cfg(target_family = "windows")]
+        let part_list = path.split(['/', '\\']).collect();
+
+        #[cfg(not(target_family = "windows"))]
+        let part_list = path.split('/').collect();
+
+        part_list
+    }
+
+    /// Expands `{a,b}` alternatives into separate patterns, character classes are left untouched
+    fn expand_braces(pattern: &str) -> Result<Vec<String>, String> {
+        let char_list : Vec<char> = pattern.chars().collect();
+
+        let mut open_index = None;
+        let mut close_index = None;
+        let mut comma_index_list = vec![];
+        let mut depth = 0;
+        let mut in_class = false;
+        for (index, symbol) in char_list.iter().enumerate() {
+            if in_class {
+                if *symbol == ']' {
+                    in_class = false;
+                }
+                continue;
+            }
+
+            match symbol {
+                '[' => in_class = true,
+                '{' => {
+                    if depth == 0 {
+                        open_index = Some(index);
+                    }
+                    depth += 1;
+                }
+                ',' if depth == 1 => comma_index_list.push(index),
+                '}' => {
+                    if depth == 0 {
+                        return Err(format!("unexpected '}}' at position {}", index))
+                    }
+                    depth -= 1;
+                    if depth == 0 {
+                        close_index = Some(index);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if open_index.is_none() {
+            return Ok(vec![pattern.to_string()])
+        }
+        if close_index.is_none() {
+            return Err("unclosed '{'".to_string())
+        }
+
+        let open_index = open_index.unwrap();
+        let close_index = close_index.unwrap();
+        let prefix : String = char_list[..open_index].iter().collect();
+        let suffix : String = char_list[close_index + 1..].iter().collect();
+
+        let mut bound_list = vec![open_index];
+        bound_list.extend(comma_index_list);
+        bound_list.push(close_index);
+
+        let mut expanded_list = vec![];
+        for bound in bound_list.windows(2) {
+            let alternative : String = char_list[bound[0] + 1..bound[1]].iter().collect();
+            let boxed_expanded = GlobPattern::expand_braces(&[prefix.as_str(), alternative.as_str(), suffix.as_str()].join(""));
+            if boxed_expanded.is_err() {
+                let message = boxed_expanded.err().unwrap();
+                return Err(message)
+            }
+            expanded_list.extend(boxed_expanded.unwrap());
+        }
+        Ok(expanded_list)
+    }
+
+    fn parse_segment(part: &str) -> Result<Segment, String> {
+        if part == "**" {
+            return Ok(Segment::AnyDepth)
+        }
+
+        let mut token_list = vec![];
+        let mut char_iterator = part.chars();
+        while let Some(symbol) = char_iterator.next() {
+            match symbol {
+                '*' => {
+                    if token_list.last() != Some(&Token::AnyString) {
+                        token_list.push(Token::AnyString);
+                    }
+                }
+                '?' => token_list.push(Token::AnyChar),
+                '[' => {
+                    let mut negated = false;
+                    let mut range_list = vec![];
+                    let mut closed = false;
+                    let mut first = true;
+                    while let Some(class_symbol) = char_iterator.next() {
+                        if first && (class_symbol == '!' || class_symbol == '^') {
+                            negated = true;
+                            first = false;
+                            continue;
+                        }
+                        if class_symbol == ']' && !(first && range_list.is_empty()) {
+                            closed = true;
+                            break;
+                        }
+                        first = false;
+
+                        if class_symbol == '-' && !range_list.is_empty() {
+                            let boxed_end = char_iterator.next();
+                            if boxed_end.is_none() {
+                                break;
+                            }
+                            let end = boxed_end.unwrap();
+                            if end == ']' {
+                                range_list.push(('-', '-'));
+                                closed = true;
+                                break;
+                            }
+                            let (start, _) = range_list.pop().unwrap();
+                            if start > end {
+                                return Err(format!("invalid range '{}-{}' in '{}'", start, end, part))
+                            }
+                            range_list.push((start, end));
+                            continue;
+                        }
+                        range_list.push((class_symbol, class_symbol));
+                    }
+                    if !closed {
+                        return Err(format!("unclosed '[' in '{}'", part))
+                    }
+                    token_list.push(Token::Class { negated, range_list });
+                }
+                _ => token_list.push(Token::Char(symbol)),
+            }
+        }
+
+        let is_literal = token_list.iter().all(|token| matches!(token, Token::Char(_)));
+        if is_literal {
+            return Ok(Segment::Literal(part.to_string()))
+        }
+        Ok(Segment::Wildcard(token_list))
+    }
+
+    /// Iterative matching with backtracking to the last `**` only, linear in the common case
+    /// and O(segments * parts) in the worst case
+    fn match_segments(segment_list: &[Segment], part_list: &[&str]) -> bool {
+        let mut segment_index = 0;
+        let mut part_index = 0;
+        // last `**` seen and the first part it has not consumed yet
+        let mut any_depth: Option<(usize, usize)> = None;
+
+        while part_index < part_list.len() {
+            if segment_index < segment_list.len() {
+                let segment = &segment_list[segment_index];
+                if *segment == Segment::AnyDepth {
+                    any_depth = Some((segment_index, part_index));
+                    segment_index += 1;
+                    continue;
+                }
+                if GlobPattern::match_segment(segment, part_list[part_index]) {
+                    segment_index += 1;
+                    part_index += 1;
+                    continue;
+                }
+            }
+
+            if any_depth.is_none() {
+                return false
+            }
+            // let the last `**` consume one more part and retry the segments after it
+            let (any_depth_index, consumed_index) = any_depth.unwrap();
+            any_depth = Some((any_depth_index, consumed_index + 1));
+            segment_index = any_depth_index + 1;
+            part_index = consumed_index + 1;
+        }
+
+        segment_list[segment_index..].iter().all(|segment| *segment == Segment::AnyDepth)
+    }
+
+    fn match_segment(segment: &Segment, part: &str) -> bool {
+        match segment {
+            Segment::AnyDepth => true,
+            Segment::Literal(literal) => literal == part,
+            Segment::Wildcard(token_list) => {
+                let char_list : Vec<char> = part.chars().collect();
+                GlobPattern::match_tokens(token_list, &char_list)
+            }
+        }
+    }
+
+    /// Same algorithm as `match_segments`, `*` is backtracked the way `**` is there
+    fn match_tokens(token_list: &[Token], char_list: &[char]) -> bool {
+        let mut token_index = 0;
+        let mut char_index = 0;
+        let mut any_string: Option<(usize, usize)> = None;
+
+        while char_index < char_list.len() {
+            if token_index < token_list.len() {
+                let token = &token_list[token_index];
+                if *token == Token::AnyString {
+                    any_string = Some((token_index, char_index));
+                    token_index += 1;
+                    continue;
+                }
+                if GlobPattern::match_token(token, char_list[char_index]) {
+                    token_index += 1;
+                    char_index += 1;
+                    continue;
+                }
+            }
+
+            if any_string.is_none() {
+                return false
+            }
+            let (any_string_index, consumed_index) = any_string.unwrap();
+            any_string = Some((any_string_index, consumed_index + 1));
+            token_index = any_string_index + 1;
+            char_index = consumed_index + 1;
+        }
+
+        token_list[token_index..].iter().all(|token| *token == Token::AnyString)
+    }
+
+    fn match_token(token: &Token, symbol: char) -> bool {
+        match token {
+            Token::AnyString | Token::AnyChar => true,
+            Token::Char(expected) => *expected == symbol,
+            Token::Class { negated, range_list } => {
+                let in_class = range_list.iter().any(|(start, end)| *start <= symbol && symbol <= *end);
+                in_class != *negated
+            }
+        }
+    }
+}
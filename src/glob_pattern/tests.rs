@@ -0,0 +1,89 @@
+use std::io::ErrorKind;
+use crate::file_ext_error::Operation;
+use crate::glob_pattern::GlobPattern;
+
+#[test]
+fn is_match() {
+    let case_list = [
+        ("*.css", "main.css", true),
+        ("*.css", "static/main.css", false),
+        ("*.css", ".hidden.css", true),
+        ("static/*.css", "static/main.css", true),
+        ("static/*.css", "static/css/main.css", false),
+        ("static/**/*.css", "static/main.css", true),
+        ("static/**/*.css", "static/css/theme/main.css", true),
+        ("static/**/*.css", "other/main.css", false),
+        ("**", "a/b/c", true),
+        ("**/c", "c", true),
+        ("a/**/b/**/c", "a/x/b/y/z/c", true),
+        ("a/**/b/**/c", "a/x/y/z/c", false),
+        ("file-?.txt", "file-1.txt", true),
+        ("file-?.txt", "file-10.txt", false),
+        ("file-[0-9].txt", "file-7.txt", true),
+        ("file-[0-9].txt", "file-a.txt", false),
+        ("file-[!0-9].txt", "file-a.txt", true),
+        ("file-[^0-9].txt", "file-7.txt", false),
+        ("file-[abc-].txt", "file--.txt", true),
+        ("[]]", "]", true),
+        ("*.{css,scss}", "main.scss", true),
+        ("*.{css,scss}", "main.less", false),
+        ("{static,public}/{css,js}/*", "public/js/app.js", true),
+        ("{a,b{c,d}}.txt", "bd.txt", true),
+        ("{a,b{c,d}}.txt", "b.txt", false),
+        ("[{]a,b[}]", "{a,b}", true),
+        ("!*.css", "main.css", false),
+        ("!*.css", "main.js", true),
+        ("/usr/*/lib", "/usr/local/lib", true),
+        ("*a*b*", "xxaxxbxx", true),
+        ("*a*b*", "xxbxxaxx", false),
+        ("*ab", "aab", true),
+        ("a*", "a", true),
+        ("**/a/**/a", "a/b/a/a", true),
+        ("**/*.css/**", "a/x.css", true),
+        ("", "", true),
+    ];
+
+    for (pattern, path, expected) in case_list {
+        let glob = GlobPattern::new(pattern).unwrap();
+        assert_eq!(expected, glob.is_match(path), "pattern: {}, path: {}", pattern, path);
+    }
+}
+
+#[test]
+fn pathological_pattern_is_fast() {
+    let star_pattern = GlobPattern::new(&["a*".repeat(30).as_str(), "b"].join("")).unwrap();
+    let long_name = "a".repeat(100);
+    assert!(!star_pattern.is_match(&long_name));
+    assert!(star_pattern.is_match(&[long_name.as_str(), "b"].join("")));
+
+    let any_depth_pattern = GlobPattern::new(&["**/a".repeat(30).as_str(), "/b"].join("")).unwrap();
+    let deep_path = vec!["a"; 100].join("/");
+    assert!(!any_depth_pattern.is_match(&deep_path));
+    assert!(any_depth_pattern.is_match(&[deep_path.as_str(), "b"].join("/")));
+}
+
+#[test]
+fn invalid_pattern() {
+    for pattern in ["file-[0-9", "*.{css,scss", "a}b", "[z-a]"] {
+        let error = GlobPattern::new(pattern).err().unwrap();
+        assert_eq!(ErrorKind::InvalidInput, error.kind());
+        assert_eq!(Operation::Glob, error.operation());
+        assert_eq!(pattern, error.path());
+    }
+}
+
+#[test]
+fn base_list() {
+    let case_list = [
+        ("static/**/*.css", vec![("static".to_string(), None)]),
+        ("static/css/*.css", vec![(["static", "css"].join(crate::path_ext_impl::PathExtImpl::get_path_separator().as_str()), Some(1))]),
+        ("*/*.css", vec![(".".to_string(), Some(2))]),
+        ("main.css", vec![(".".to_string(), Some(1))]),
+        ("{a,b}/*", vec![("a".to_string(), Some(1)), ("b".to_string(), Some(1))]),
+        ("!a/*", vec![(".".to_string(), None)]),
+    ];
+
+    for (pattern, expected) in case_list {
+        assert_eq!(expected, GlobPattern::new(pattern).unwrap().base_list(), "pattern: {}", pattern);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use crate::directory_entry::{DirectoryEntry, ListDirectoryOptions};
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::directory_walker::DirectoryWalker;
+use crate::file_ext_error::FileExtError;
+use crate::glob_pattern::GlobPattern;
+use crate::path_ext_impl::PathExtImpl;
+
+#[cfg(test)]
+mod tests;
+
+/// Lazily finds entries matching the glob pattern. Only the literal prefix of the pattern
+/// is walked (for example `static` for `static/**/*.css`), and no deeper than the pattern allows.
+///
+/// Paths of returned entries are relative in the same way as the pattern is,
+/// pattern `*.css` gives `main.css`, pattern `static/*.css` gives `static/main.css`.
+/// Symlinks are not followed, missing base directory gives no matches.
+pub struct GlobWalker {
+    pattern: GlobPattern,
+    // directories left to walk, in reversed order
+    base_list: Vec<(String, Option<usize>)>,
+    current: Option<(String, DirectoryWalker)>,
+    // set only if there are several base directories, as their walks may overlap
+    yielded_path_set: Option<HashSet<String>>,
+}
+
+impl GlobWalker {
+    pub(crate) fn new(pattern: GlobPattern) -> GlobWalker {
+        let mut base_list = pattern.base_list();
+        let yielded_path_set = if base_list.len() > 1 { Some(HashSet::new()) } else { None };
+        base_list.reverse();
+
+        GlobWalker {
+            pattern,
+            base_list,
+            current: None,
+            yielded_path_set,
+        }
+    }
+
+    pub fn pattern(&self) -> &GlobPattern {
+        &self.pattern
+    }
+
+    fn next_walker(&mut self) -> Option<Result<(), FileExtError>> {
+        let (base, max_depth) = self.base_list.pop()?;
+        if !DirectoryExtImpl::does_directory_exist(&base) {
+            return Some(Ok(()))
+        }
+
+        let options = ListDirectoryOptions { max_depth, ..ListDirectoryOptions::default() };
+        let boxed_walker = DirectoryExtImpl::walk_directory(&base, &options);
+        if boxed_walker.is_err() {
+            let error = boxed_walker.err().unwrap();
+            return Some(Err(error))
+        }
+
+        self.current = Some((base, boxed_walker.unwrap()));
+        Some(Ok(()))
+    }
+}
+
+impl Iterator for GlobWalker {
+    type Item = Result<DirectoryEntry, FileExtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current.is_none() {
+                let boxed_next_walker = self.next_walker()?;
+                if boxed_next_walker.is_err() {
+                    let error = boxed_next_walker.err().unwrap();
+                    return Some(Err(error))
+                }
+                continue;
+            }
+
+            let (base, walker) = self.current.as_mut().unwrap();
+            let boxed_entry = walker.next();
+            if boxed_entry.is_none() {
+                self.current = None;
+                continue;
+            }
+
+            let boxed_entry = boxed_entry.unwrap();
+            if boxed_entry.is_err() {
+                return Some(boxed_entry)
+            }
+
+            let mut entry = boxed_entry.unwrap();
+            if base == "." {
+                let prefix = [".", PathExtImpl::get_path_separator().as_str()].join("");
+                if let Some(relative_path) = entry.path.strip_prefix(&prefix) {
+                    entry.path = relative_path.to_string();
+                }
+                if let Ok(relative_path) = entry.os_path.strip_prefix(".") {
+                    entry.os_path = relative_path.to_path_buf();
+                }
+            }
+
+            if !self.pattern.is_match(&entry.path) {
+                continue;
+            }
+
+            if let Some(yielded_path_set) = self.yielded_path_set.as_mut() {
+                if !yielded_path_set.insert(entry.path.clone()) {
+                    continue;
+                }
+            }
+
+            return Some(Ok(entry))
+        }
+    }
+}
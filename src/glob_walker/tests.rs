@@ -0,0 +1,59 @@
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_impl::FileExtImpl;
+use crate::glob_pattern::GlobPattern;
+use crate::glob_walker::GlobWalker;
+use crate::path_ext_impl::PathExtImpl;
+use crate::write_mode::WriteMode;
+
+fn create_tree(root: &str) {
+    if DirectoryExtImpl::does_directory_exist(root) {
+        DirectoryExtImpl::delete_directory(root).unwrap();
+    }
+
+    DirectoryExtImpl::create_directory(PathExtImpl::build_path(&[root, "css", "theme"])).unwrap();
+    std::fs::create_dir(PathExtImpl::build_path(&[root, "js"])).unwrap();
+    let file_list = [
+        PathExtImpl::build_path(&[root, "main.css"]),
+        PathExtImpl::build_path(&[root, "index.html"]),
+        PathExtImpl::build_path(&[root, "css", "site.css"]),
+        PathExtImpl::build_path(&[root, "css", "site.scss"]),
+        PathExtImpl::build_path(&[root, "css", "theme", "dark.css"]),
+        PathExtImpl::build_path(&[root, "js", "app.js"]),
+    ];
+    for path in file_list {
+        FileExtImpl::write_file_with_mode(&path, "content".as_bytes(), WriteMode::CreateNew).unwrap();
+    }
+}
+
+fn glob(pattern: &str) -> Vec<String> {
+    GlobWalker::new(GlobPattern::new(pattern).unwrap())
+        .map(|boxed_entry| boxed_entry.unwrap().path.replace('\\', "/"))
+        .collect()
+}
+
+#[test]
+fn glob_walk() {
+    let root = "glob_walk_tree";
+    create_tree(root);
+
+    assert_eq!(
+        vec!["glob_walk_tree/css/site.css", "glob_walk_tree/css/theme/dark.css", "glob_walk_tree/main.css"],
+        glob("glob_walk_tree/**/*.css"),
+    );
+    assert_eq!(vec!["glob_walk_tree/main.css"], glob("glob_walk_tree/*.css"));
+    assert_eq!(
+        vec!["glob_walk_tree/css/site.css", "glob_walk_tree/css/site.scss", "glob_walk_tree/js/app.js"],
+        glob("glob_walk_tree/{css,js}/*.{css,scss,js}"),
+    );
+    assert_eq!(vec!["glob_walk_tree/css", "glob_walk_tree/js"], glob("glob_walk_tree/[c-j]s*"));
+    assert_eq!(vec!["glob_walk_tree/css/theme/dark.css"], glob("glob_walk_*/*/theme/*"));
+    assert!(glob("glob_walk_nonexistent/**").is_empty());
+
+    // overlapping base directories do not give duplicates
+    assert_eq!(
+        vec!["glob_walk_tree/css/site.css", "glob_walk_tree/css/theme/dark.css", "glob_walk_tree/main.css"],
+        glob("{glob_walk_tree,glob_walk_tree/css}/**/*.css"),
+    );
+
+    DirectoryExtImpl::delete_directory(root).unwrap();
+}
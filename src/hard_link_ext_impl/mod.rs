@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::file_ext_impl::FileExtImpl;
+use crate::filter_string::FilterString;
+use crate::symlink_ext_impl::SymlinkExtImpl;
+
+#[cfg(test)]
+mod tests;
+
+pub struct HardLinkExtImpl;
+
+impl HardLinkExtImpl {
+    pub fn create_hard_link<P: AsRef<Path>, Q: AsRef<Path>>(existing_path: P, new_path: Q) -> Result<(), FileExtError> {
+        let existing = existing_path.as_ref();
+        let new = new_path.as_ref();
+        let existing_path = existing.to_string_lossy();
+        let new_path = new.to_string_lossy();
+        for path in [existing, new] {
+            let boxed_check = FilterString::is_valid_input_path(path);
+            if boxed_check.is_err() {
+                let message = boxed_check.err().unwrap();
+                return Err(FileExtError::invalid_path(Operation::CreateHardLink, &path.to_string_lossy(), message))
+            }
+        }
+
+        //check if there is already a file where hard link is going to be created
+        let is_occupied = FileExtImpl::does_file_exist(new)
+            || DirectoryExtImpl::does_directory_exist(new)
+            || SymlinkExtImpl::does_symlink_exist(new);
+        if is_occupied {
+            let message = format!("There is already a file or directory at the given path: {}", new_path);
+            return Err(FileExtError::from_kind(Operation::CreateHardLink, &new_path, ErrorKind::AlreadyExists, message))
+        }
+
+        // file checks below follow symlinks while hard link is created to the symlink itself
+        if SymlinkExtImpl::does_symlink_exist(existing) {
+            let message = format!("Hard link can not point to a symlink, resolve it first: {}", existing_path);
+            return Err(FileExtError::from_kind(Operation::CreateHardLink, &existing_path, ErrorKind::InvalidInput, message))
+        }
+
+        if DirectoryExtImpl::does_directory_exist(existing) {
+            let message = format!("Hard link can not point to a directory: {}", existing_path);
+            return Err(FileExtError::from_kind(Operation::CreateHardLink, &existing_path, ErrorKind::InvalidInput, message))
+        }
+
+        if !FileExtImpl::does_file_exist(existing) {
+            let message = format!("There is no file for hard link to be created: {}", existing_path);
+            return Err(FileExtError::from_kind(Operation::CreateHardLink, &existing_path, ErrorKind::NotFound, message))
+        }
+
+        let boxed_link = fs::hard_link(existing, new);
+        if boxed_link.is_err() {
+            let error = boxed_link.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CreateHardLink, &new_path, error))
+        }
+
+        Ok(())
+    }
+
+    #[cfg(target_family = "unix")]
+    pub fn hard_link_count<P: AsRef<Path>>(path: P) -> Result<u64, FileExtError> {
+        use std::os::unix::fs::MetadataExt;
+
+        let path = path.as_ref();
+        let boxed_metadata = fs::metadata(path);
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &path.to_string_lossy(), error))
+        }
+
+        Ok(boxed_metadata.unwrap().nlink())
+    }
+
+    /// Compares device and inode, symlinks are followed
+    #[cfg(target_family = "unix")]
+    pub fn are_same_file<P: AsRef<Path>, Q: AsRef<Path>>(path: P, other_path: Q) -> Result<bool, FileExtError> {
+        use std::os::unix::fs::MetadataExt;
+
+        let path = path.as_ref();
+        let other_path = other_path.as_ref();
+        let boxed_metadata = fs::metadata(path);
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &path.to_string_lossy(), error))
+        }
+
+        let boxed_other_metadata = fs::metadata(other_path);
+        if boxed_other_metadata.is_err() {
+            let error = boxed_other_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &other_path.to_string_lossy(), error))
+        }
+
+        let metadata = boxed_metadata.unwrap();
+        let other_metadata = boxed_other_metadata.unwrap();
+        Ok(metadata.dev() == other_metadata.dev() && metadata.ino() == other_metadata.ino())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::FileExtError;
+use crate::file_ext_impl::FileExtImpl;
+use crate::hard_link_ext_impl::HardLinkExtImpl;
+use crate::path_ext_impl::PathExtImpl;
+
+#[test]
+fn hard_link_shares_content() {
+    let directory = "hard_link_shares_content";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+    let asset = PathExtImpl::build_path(&[directory, "asset.css"]);
+    let duplicate = PathExtImpl::build_path(&[directory, "duplicate.css"]);
+    FileExtImpl::create_file(&asset).unwrap();
+    FileExtImpl::write_file(&asset, b"body {}").unwrap();
+
+    HardLinkExtImpl::create_hard_link(&asset, &duplicate).unwrap();
+    FileExtImpl::write_file(&duplicate, b"\np {}").unwrap();
+    assert_eq!(b"body {}\np {}".to_vec(), FileExtImpl::read_file(&asset).unwrap());
+
+    // removing one name keeps the content reachable via the other
+    FileExtImpl::delete_file(&asset).unwrap();
+    assert_eq!(b"body {}\np {}".to_vec(), FileExtImpl::read_file(&duplicate).unwrap());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+fn hard_link_validation() {
+    let directory = "hard_link_validation";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+    let asset = PathExtImpl::build_path(&[directory, "asset.css"]);
+    FileExtImpl::create_file(&asset).unwrap();
+
+    let error = HardLinkExtImpl::create_hard_link(&asset, &asset).err().unwrap();
+    assert!(matches!(error, FileExtError::AlreadyExists { .. }));
+
+    let missing = PathExtImpl::build_path(&[directory, "missing.css"]);
+    let link = PathExtImpl::build_path(&[directory, "link.css"]);
+    let error = HardLinkExtImpl::create_hard_link(&missing, &link).err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+
+    let error = HardLinkExtImpl::create_hard_link(directory, &link).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    let error = HardLinkExtImpl::create_hard_link(&asset, "hard_link_validation/link;.css").err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn hard_link_to_symlink_is_rejected() {
+    let directory = "hard_link_to_symlink_is_rejected";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+    let asset = PathExtImpl::build_path(&[directory, "asset.css"]);
+    let symlink = PathExtImpl::build_path(&[directory, "symlink.css"]);
+    let link = PathExtImpl::build_path(&[directory, "link.css"]);
+    FileExtImpl::create_file(&asset).unwrap();
+    std::os::unix::fs::symlink("asset.css", &symlink).unwrap();
+
+    let error = HardLinkExtImpl::create_hard_link(&symlink, &link).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+    assert!(!FileExtImpl::does_file_exist(&link));
+
+    let dangling = PathExtImpl::build_path(&[directory, "dangling.css"]);
+    std::os::unix::fs::symlink("missing.css", &dangling).unwrap();
+    let error = HardLinkExtImpl::create_hard_link(&dangling, &link).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn link_count_and_same_file() {
+    let directory = "link_count_and_same_file";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+    let asset = PathExtImpl::build_path(&[directory, "asset.css"]);
+    let duplicate = PathExtImpl::build_path(&[directory, "duplicate.css"]);
+    let copy = PathExtImpl::build_path(&[directory, "copy.css"]);
+    FileExtImpl::create_file(&asset).unwrap();
+    FileExtImpl::create_file(&copy).unwrap();
+
+    assert_eq!(1, HardLinkExtImpl::hard_link_count(&asset).unwrap());
+    HardLinkExtImpl::create_hard_link(&asset, &duplicate).unwrap();
+    assert_eq!(2, HardLinkExtImpl::hard_link_count(&asset).unwrap());
+    assert_eq!(2, HardLinkExtImpl::hard_link_count(&duplicate).unwrap());
+
+    assert!(HardLinkExtImpl::are_same_file(&asset, &duplicate).unwrap());
+    assert!(!HardLinkExtImpl::are_same_file(&asset, &copy).unwrap());
+
+    let symlink = PathExtImpl::build_path(&[directory, "symlink.css"]);
+    std::os::unix::fs::symlink("asset.css", &symlink).unwrap();
+    assert!(HardLinkExtImpl::are_same_file(&symlink, &duplicate).unwrap());
+
+    let error = HardLinkExtImpl::hard_link_count("link_count_and_same_file/missing.css").err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
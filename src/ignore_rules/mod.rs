@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::Path;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::glob_pattern::GlobPattern;
+
+#[cfg(test)]
+mod tests;
+
+/// Rules in `.gitignore` syntax, deciding which entries are skipped by directory listing, copy and delete.
+///
+/// Supported syntax:
+/// - blank lines and lines starting with `#` are skipped, `\#` and `\!` escape the first character
+/// - `!pattern` re-includes entries excluded by previous rules, unless their parent directory is excluded
+/// - `pattern/` matches only directories
+/// - pattern with `/` at the beginning or in the middle is anchored to the directory of the ignore file,
+///   otherwise it matches at any depth
+/// - `*`, `?`, `[a-z]` and `**` as in `GlobPattern`, trailing `dir/**` matches the content of `dir` but not `dir` itself
+///
+/// Rules added directly apply to the root of the walked tree. If ignore file names are set
+/// (for example `.gitignore`), such files are read from every walked directory and their rules
+/// apply to the content of that directory. Last matching rule wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoreRules {
+    rule_list: Vec<IgnoreRule>,
+    ignore_file_name_list: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRule {
+    // directory the rule is defined in, relative to the root, segments separated by '/'
+    base: String,
+    pattern: GlobPattern,
+    negated: bool,
+    directory_only: bool,
+}
+
+impl IgnoreRules {
+    pub fn new() -> IgnoreRules {
+        IgnoreRules::default()
+    }
+
+    /// Parses content of the ignore file, rules apply to the root of the walked tree
+    pub fn parse(content: &str) -> Result<IgnoreRules, FileExtError> {
+        let mut ignore_rules = IgnoreRules::new();
+        let boxed_add = ignore_rules.add_rules("", content);
+        if boxed_add.is_err() {
+            let error = boxed_add.err().unwrap();
+            return Err(error)
+        }
+        Ok(ignore_rules)
+    }
+
+    /// Adds single rule, for example `target/`, applied to the root of the walked tree
+    pub fn add_rule(&mut self, line: &str) -> Result<(), FileExtError> {
+        self.add_rules("", line)
+    }
+
+    /// Adds rules from the content of the ignore file located in the `base` directory, relative to the walked root
+    pub fn add_rules(&mut self, base: &str, content: &str) -> Result<(), FileExtError> {
+        let base = IgnoreRules::split_path(base).join("/");
+        for line in content.lines() {
+            let boxed_rule = IgnoreRules::parse_rule(&base, line);
+            if boxed_rule.is_err() {
+                let error = boxed_rule.err().unwrap();
+                return Err(error)
+            }
+
+            if let Some(rule) = boxed_rule.unwrap() {
+                self.rule_list.push(rule);
+            }
+        }
+        Ok(())
+    }
+
+    /// Name of the file with rules to be read from each walked directory, for example `.gitignore` or `.ignore`
+    pub fn add_ignore_file_name(&mut self, name: &str) {
+        self.ignore_file_name_list.push(name.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rule_list.is_empty() && self.ignore_file_name_list.is_empty()
+    }
+
+    /// Checks if the entry is ignored. Path is relative to the walked root, entry is ignored
+    /// if it matches the rules itself or if any of its parent directories does
+    pub fn is_ignored(&self, relative_path: &str, is_directory: bool) -> bool {
+        let part_list = IgnoreRules::split_path(relative_path);
+        if part_list.is_empty() {
+            return false
+        }
+
+        for parent_length in 1..part_list.len() {
+            let parent = part_list[..parent_length].join("/");
+            if self.match_rules(&parent, true) == Some(true) {
+                return true
+            }
+        }
+
+        self.match_rules(&part_list.join("/"), is_directory) == Some(true)
+    }
+
+    /// Reads ignore files from the directory, `relative_directory` is path of the directory relative to the walked root
+    pub(crate) fn load_ignore_files(&mut self, directory: &Path, relative_directory: &str) -> Result<(), FileExtError> {
+        for name in self.ignore_file_name_list.clone() {
+            let path = directory.join(&name);
+            if !path.is_file() {
+                continue;
+            }
+
+            let boxed_content = fs::read_to_string(&path);
+            if boxed_content.is_err() {
+                let error = boxed_content.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Read, &path.to_string_lossy(), error))
+            }
+
+            let boxed_add = self.add_rules(relative_directory, &boxed_content.unwrap());
+            if boxed_add.is_err() {
+                let error = boxed_add.err().unwrap();
+                return Err(error)
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns Some(true) if the last matching rule excludes the path, Some(false) if it re-includes it
+    fn match_rules(&self, path: &str, is_directory: bool) -> Option<bool> {
+        let mut is_ignored = None;
+        for rule in self.rule_list.iter() {
+            if rule.directory_only && !is_directory {
+                continue;
+            }
+
+            let relative_path = if rule.base.is_empty() {
+                path
+            } else {
+                let boxed_relative_path = path
+                    .strip_prefix(rule.base.as_str())
+                    .and_then(|remaining| remaining.strip_prefix('/'));
+                if boxed_relative_path.is_none() {
+                    continue;
+                }
+                boxed_relative_path.unwrap()
+            };
+
+            if rule.pattern.is_match(relative_path) {
+                is_ignored = Some(!rule.negated);
+            }
+        }
+        is_ignored
+    }
+
+    fn parse_rule(base: &str, line: &str) -> Result<Option<IgnoreRule>, FileExtError> {
+        let line = IgnoreRules::trim_trailing_spaces(line);
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None)
+        }
+
+        // `\#` and `\!` are handled by the escape below
+        let (negated, mut pattern) = match line.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, line),
+        };
+
+        let directory_only = pattern.ends_with('/');
+        if directory_only {
+            pattern = pattern.trim_end_matches('/');
+        }
+        if pattern.is_empty() {
+            return Ok(None)
+        }
+
+        let anchored = pattern.contains('/');
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+        let mut glob = IgnoreRules::escape(pattern);
+        // trailing `/**` matches everything inside the directory, but not the directory itself
+        if glob.ends_with("/**") {
+            glob.push_str("/*");
+        }
+        if !anchored {
+            glob = ["**/", glob.as_str()].join("");
+        }
+
+        let boxed_pattern = GlobPattern::new(&glob);
+        if boxed_pattern.is_err() {
+            let error = boxed_pattern.err().unwrap();
+            return Err(error)
+        }
+
+        Ok(Some(IgnoreRule {
+            base: base.to_string(),
+            pattern: boxed_pattern.unwrap(),
+            negated,
+            directory_only,
+        }))
+    }
+
+    /// Converts gitignore escapes into glob character classes, braces are literal in gitignore
+    fn escape(pattern: &str) -> String {
+        let mut glob = String::new();
+        let mut char_iterator = pattern.chars();
+        while let Some(symbol) = char_iterator.next() {
+            match symbol {
+                '\\' => {
+                    if let Some(escaped) = char_iterator.next() {
+                        match escaped {
+                            '*' | '?' | '[' | '{' | '}' => glob.push_str(&format!("[{}]", escaped)),
+                            // leading `!` would negate the glob pattern, single brace alternative keeps it literal
+                            '!' if glob.is_empty() => glob.push_str("{!}"),
+                            _ => glob.push(escaped),
+                        }
+                    }
+                }
+                '{' | '}' => glob.push_str(&format!("[{}]", symbol)),
+                _ => glob.push(symbol),
+            }
+        }
+        glob
+    }
+
+    fn trim_trailing_spaces(line: &str) -> &str {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        let mut end = trimmed.len();
+        while trimmed[..end].ends_with(' ') && !trimmed[..end - 1].ends_with('\\') {
+            end -= 1;
+        }
+        &trimmed[..end]
+    }
+
+    #[cfg(target_family = "windows")]
+    fn split_path(path: &str) -> Vec<&str> {
+        path.split(['/', '\\']).filter(|part| !part.is_empty() && *part != ".").collect()
+    }
+
+    #[cfg(not(target_family = "windows"))]
+    fn split_path(path: &str) -> Vec<&str> {
+        path.split('/').filter(|part| !part.is_empty() && *part != ".").collect()
+    }
+}
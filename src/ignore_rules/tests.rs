@@ -0,0 +1,110 @@
+use std::io::ErrorKind;
+use crate::ignore_rules::IgnoreRules;
+
+#[test]
+fn is_ignored() {
+    let content = "
+# build output
+target/
+*.log
+!important.log
+/config.local
+doc/*.txt
+**/cache/**
+\\#hash
+\\!bang
+space\\ 
+[abc].tmp
+literal{brace}
+";
+    let ignore_rules = IgnoreRules::parse(content).unwrap();
+
+    let case_list = [
+        ("target", true, true),
+        ("target", false, false),
+        ("target/debug/build.rs", false, true),
+        ("sub/target", true, true),
+        ("sub/target/file", false, true),
+        ("error.log", false, true),
+        ("logs/error.log", false, true),
+        ("important.log", false, false),
+        ("logs/important.log", false, false),
+        ("config.local", false, true),
+        ("sub/config.local", false, false),
+        ("doc/readme.txt", false, true),
+        ("doc/sub/readme.txt", false, false),
+        ("sub/doc/readme.txt", false, false),
+        ("a/cache/b", false, true),
+        ("cache/b", false, true),
+        ("a/cache", true, false),
+        ("#hash", false, true),
+        ("!bang", false, true),
+        ("space ", false, true),
+        ("space", false, false),
+        ("b.tmp", false, true),
+        ("d.tmp", false, false),
+        ("literal{brace}", false, true),
+        ("src/main.rs", false, false),
+        ("", true, false),
+    ];
+
+    for (path, is_directory, expected) in case_list {
+        assert_eq!(expected, ignore_rules.is_ignored(path, is_directory), "path: {}", path);
+    }
+}
+
+#[test]
+fn excluded_parent_can_not_be_reincluded() {
+    let ignore_rules = IgnoreRules::parse("build/\n!build/keep.txt\n").unwrap();
+    assert!(ignore_rules.is_ignored("build/keep.txt", false));
+
+    let ignore_rules = IgnoreRules::parse("build/*\n!build/keep.txt\n").unwrap();
+    assert!(!ignore_rules.is_ignored("build/keep.txt", false));
+    assert!(ignore_rules.is_ignored("build/other.txt", false));
+}
+
+#[test]
+fn trailing_double_star_matches_only_content() {
+    let ignore_rules = IgnoreRules::parse("foo/**\n").unwrap();
+    assert!(!ignore_rules.is_ignored("foo", true));
+    assert!(ignore_rules.is_ignored("foo/bar.txt", false));
+    assert!(ignore_rules.is_ignored("foo/sub/bar.txt", false));
+
+    let ignore_rules = IgnoreRules::parse("!foo\nfoo/**\n").unwrap();
+    assert!(!ignore_rules.is_ignored("foo", true));
+    assert!(ignore_rules.is_ignored("foo/bar.txt", false));
+
+    let ignore_rules = IgnoreRules::parse("foo/**\n!foo\n").unwrap();
+    assert!(!ignore_rules.is_ignored("foo", true));
+    assert!(ignore_rules.is_ignored("foo/bar.txt", false));
+}
+
+#[test]
+fn nested_rules() {
+    let mut ignore_rules = IgnoreRules::new();
+    ignore_rules.add_rule("*.tmp").unwrap();
+    ignore_rules.add_rules("sub", "/local.txt\n!keep.tmp\n").unwrap();
+
+    assert!(ignore_rules.is_ignored("a.tmp", false));
+    assert!(ignore_rules.is_ignored("sub/a.tmp", false));
+    assert!(!ignore_rules.is_ignored("sub/keep.tmp", false));
+    assert!(ignore_rules.is_ignored("keep.tmp", false));
+    assert!(ignore_rules.is_ignored("sub/local.txt", false));
+    assert!(!ignore_rules.is_ignored("sub/deeper/local.txt", false));
+    assert!(!ignore_rules.is_ignored("local.txt", false));
+}
+
+#[test]
+fn is_empty() {
+    let mut ignore_rules = IgnoreRules::new();
+    assert!(ignore_rules.is_empty());
+
+    ignore_rules.add_ignore_file_name(".gitignore");
+    assert!(!ignore_rules.is_empty());
+}
+
+#[test]
+fn invalid_rule() {
+    let error = IgnoreRules::parse("file-[0-9").err().unwrap();
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+}
@@ -1,8 +1,10 @@
-use std::fs::{File};
-use crate::date_time_ext::DateTimeExt;
+#![allow(clippy::test_attr_in_doctest)]
+
 use crate::directory_ext_impl::DirectoryExtImpl;
 use crate::file_ext_impl::FileExtImpl;
+use crate::hard_link_ext_impl::HardLinkExtImpl;
 use crate::path_ext_impl::PathExtImpl;
+use crate::resumable_copy_impl::ResumableCopyImpl;
 use crate::symbol::SYMBOL;
 use crate::symlink_ext_impl::SymlinkExtImpl;
 use crate::user_ext_impl::UserExtImpl;
@@ -15,8 +17,50 @@ mod file_ext_impl;
 mod path_ext_impl;
 mod directory_ext_impl;
 mod symlink_ext_impl;
+mod hard_link_ext_impl;
 mod user_ext_impl;
 mod filter_string;
+mod file_ext_error;
+mod file_reader;
+mod byte_range;
+mod write_mode;
+mod file_range;
+mod resumable_copy_impl;
+mod copy_directory_options;
+mod delete_directory_options;
+mod directory_entry;
+mod directory_walker;
+mod glob_pattern;
+mod glob_walker;
+mod ignore_rules;
+mod path_style;
+mod path_policy;
+mod file_ext_path;
+mod temp_entry;
+mod user_info;
+mod symlink_options;
+mod symlink_chain;
+
+pub use crate::byte_range::{ByteRange, ByteRangeContent};
+pub use crate::copy_directory_options::{CopyDirectoryOptions, CopyDirectoryStatus, SymlinkPolicy};
+pub use crate::delete_directory_options::{DeleteDirectoryOptions, DeleteDirectoryReport};
+pub use crate::directory_entry::{DirectoryEntry, EntryKind, ListDirectoryOptions, SortBy};
+pub use crate::directory_walker::DirectoryWalker;
+pub use crate::file_ext_error::{FileExtError, Operation, PathRejection};
+pub use crate::file_ext_path::FileExtPath;
+pub use crate::file_range::FileRange;
+pub use crate::file_reader::FileReader;
+pub use crate::glob_pattern::GlobPattern;
+pub use crate::glob_walker::GlobWalker;
+pub use crate::ignore_rules::IgnoreRules;
+pub use crate::path_policy::{PathPolicy, PathRules, PathViolation};
+pub use crate::path_style::PathStyle;
+pub use crate::resumable_copy_impl::ResumableCopyStatus;
+pub use crate::symlink_chain::SymlinkChain;
+pub use crate::symlink_options::{SymlinkKind, SymlinkOptions};
+pub use crate::temp_entry::{TempDirectory, TempFile};
+pub use crate::user_info::UserInfo;
+pub use crate::write_mode::WriteMode;
 
 pub struct FileExt;
 
@@ -38,10 +82,38 @@ impl FileExt {
     ///     assert_eq!(expected_content, content);
     /// }
     /// ```
-    pub fn read_file_partially(filepath: &str, start: u64, end: u64) -> Result<Vec<u8>, String> {
+    pub fn read_file_partially(filepath: &str, start: u64, end: u64) -> Result<Vec<u8>, FileExtError> {
         FileExtImpl::read_file_partially(filepath, start, end)
     }
 
+    /// Returns several portions of a file, as requested by http `Range` header.
+    /// Ranges are validated against file length: unsatisfiable ranges are skipped,
+    /// overlapping and adjacent ranges are merged. File is opened only once.
+    /// Ranges are read into memory, so their total length is limited to `ByteRange::MAX_TOTAL_LENGTH`,
+    /// larger requests fail with `InvalidInput`.
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::{ByteRange, FileExt};
+    /// #[test]
+    /// fn partial_read_multiple_ranges() {
+    ///     let path = "test/index.html";
+    ///     let range_list = ByteRange::parse_header("bytes=4-10,-7").unwrap();
+    ///     let range_content_list = FileExt::read_file_ranges(path, &range_list).unwrap();
+    ///
+    ///     assert_eq!("CTYPE h", String::from_utf8(range_content_list[0].content.clone()).unwrap());
+    ///     assert_eq!("</html>", String::from_utf8(range_content_list[1].content.clone()).unwrap());
+    /// }
+    /// ```
+    pub fn read_file_ranges(filepath: &str, range_list: &[ByteRange]) -> Result<Vec<ByteRangeContent>, FileExtError> {
+        FileExtImpl::read_file_ranges(filepath, range_list)
+    }
+
+    /// Same as `read_file_ranges`, returns result as a `multipart/byteranges` body separated by the given boundary
+    pub fn read_file_ranges_as_multipart(filepath: &str, range_list: &[ByteRange], content_type: &str, boundary: &str) -> Result<Vec<u8>, FileExtError> {
+        FileExtImpl::read_file_ranges_as_multipart(filepath, range_list, content_type, boundary)
+    }
+
     /// Returns file content
     /// # Examples
     ///
@@ -60,10 +132,35 @@ impl FileExt {
     ///      assert_eq!(expected_content, content_escaped_newline_carriage_return);
     ///  }
     /// ```
-    pub fn read_file(filepath: &str) -> Result<Vec<u8>, String> {
+    pub fn read_file(filepath: &str) -> Result<Vec<u8>, FileExtError> {
         FileExtImpl::read_file(filepath)
     }
 
+    /// Opens file for streaming read. File is read in chunks of `chunk_size` bytes, if chunk size is None it is set to 100kb.
+    /// Returned reader implements `std::io::Read` and `Iterator` over chunks.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::FileExt;
+    ///  #[test]
+    ///  fn read_in_chunks() {
+    ///      let path = "test/index.html";
+    ///      let reader = FileExt::open_reader(path, Some(16)).unwrap();
+    ///
+    ///      let mut content = vec![];
+    ///      for boxed_chunk in reader {
+    ///          let chunk = boxed_chunk.unwrap();
+    ///          assert!(chunk.len() <= 16);
+    ///          content.extend_from_slice(&chunk);
+    ///      }
+    ///
+    ///      assert_eq!(FileExt::read_file(path).unwrap(), content);
+    ///  }
+    /// ```
+    pub fn open_reader(filepath: &str, chunk_size: Option<usize>) -> Result<FileReader, FileExtError> {
+        FileExtImpl::open_reader(filepath, chunk_size)
+    }
+
     /// Returns file modification timestamp as nanoseconds in Unix epoch
     /// # Examples
     ///
@@ -98,31 +195,8 @@ impl FileExt {
     ///      assert!(doesnt_exist);
     ///  }
     /// ```
-    pub fn file_modified_utc(filepath: &str) -> Result<u128, String> {
-        let boxed_open = File::open(filepath);
-        if boxed_open.is_err() {
-            let error_msg = boxed_open.err().unwrap();
-            let error = format!("<p>Unable to open file: {}</p> <p>error: {}</p>", filepath, error_msg);
-            return Err(error)
-        }
-
-        let file : File = boxed_open.unwrap();
-        let boxed_metadata = file.metadata();
-        if boxed_metadata.is_err() {
-            let error_msg = boxed_metadata.err().unwrap();
-            let error = format!("<p>Unable to open file: {}</p> <p>error: {}</p>", filepath, error_msg);
-            return Err(error)
-        }
-        let metadata = boxed_metadata.unwrap();
-        let boxed_last_modified_time = metadata.modified();
-        if boxed_last_modified_time.is_err() {
-            let error_msg = boxed_last_modified_time.err().unwrap();
-            let error = format!("<p>Unable to open file: {}</p> <p>error: {}</p>", filepath, error_msg);
-            return Err(error)
-        }
-        let modified_time = boxed_last_modified_time.unwrap();
-        let nanos = DateTimeExt::_system_time_to_unix_nanos(modified_time);
-        Ok(nanos)
+    pub fn file_modified_utc(filepath: &str) -> Result<u128, FileExtError> {
+        FileExtImpl::file_modified_utc(filepath)
     }
 
     #[cfg(any(target_family = "unix", target_family = "wasm"))]
@@ -165,7 +239,7 @@ impl FileExt {
     ///     let path = boxed_path.unwrap();
     /// }
     /// ```
-    pub fn working_directory() -> Result<String, String> {
+    pub fn working_directory() -> Result<String, FileExtError> {
         PathExtImpl::working_directory()
     }
 
@@ -182,12 +256,80 @@ impl FileExt {
     ///     let path = boxed_path.unwrap();
     /// }
     /// ```
-    pub fn absolute_path_to_working_directory() -> Result<String, String> {
+    pub fn absolute_path_to_working_directory() -> Result<String, FileExtError> {
         PathExtImpl::working_directory()
     }
 
 
-    /// Will return absolute working directory path appended to the given string
+    /// Sets `PathPolicy` all paths given to `FileExt` functions are checked against, for the whole process.
+    /// Default policy uses `PathRules::Strict`, it rejects everything `PathRules::Portable` does (NUL and control
+    /// characters, reserved names, trailing dots, long components) plus whitespace and other shell metacharacters. `..` components are allowed unless `reject_parent_traversal` is set.
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::{FileExt, PathPolicy, PathRules};
+    /// #[test]
+    /// fn path_policy() {
+    ///     let path = "report 1.txt";
+    ///     assert!(FileExt::create_file(path).is_err());
+    ///
+    ///     FileExt::set_path_policy(PathPolicy::new(PathRules::Portable));
+    ///     FileExt::create_file(path).unwrap();
+    ///     FileExt::delete_file(path).unwrap();
+    ///
+    ///     FileExt::set_path_policy(PathPolicy { rules: PathRules::Portable, reject_parent_traversal: true });
+    ///     assert!(FileExt::read_file("../file-ext/Cargo.toml").is_err());
+    /// }
+    /// ```
+    pub fn set_path_policy(policy: PathPolicy) {
+        PathPolicy::set_default_policy(policy)
+    }
+
+    /// Runs the function with the given `PathPolicy` applied to `FileExt` calls made on the current thread
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::{FileExt, PathPolicy, PathRules};
+    /// #[test]
+    /// fn scoped_path_policy() {
+    ///     let portable = PathPolicy::new(PathRules::Portable);
+    ///     let boxed_create = FileExt::with_path_policy(portable, || FileExt::create_file("report 2.txt"));
+    ///     assert!(boxed_create.is_ok());
+    ///
+    ///     assert!(FileExt::delete_file("report 2.txt").is_err());
+    ///     FileExt::with_path_policy(portable, || FileExt::delete_file("report 2.txt")).unwrap();
+    /// }
+    /// ```
+    pub fn with_path_policy<T, F: FnOnce() -> T>(policy: PathPolicy, function: F) -> T {
+        PathPolicy::with_scoped(policy, function)
+    }
+
+    /// Resolves untrusted path (for example path from http request) within the root directory.
+    /// Path is normalized, leading separators are ignored, so `/index.html` is `index.html` in the root.
+    /// Path which leads outside of the root via `..` or has a drive prefix is rejected.
+    /// If `resolve_symlinks` is set, symlinks are resolved and the result is checked to be still within the root,
+    /// returned path is canonical then. Path is not required to exist.
+    ///
+    /// Rejection reason is available via `FileExtError::rejection`.
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::{FileExt, PathRejection};
+    /// #[test]
+    /// fn resolve_within() {
+    ///     let path = FileExt::resolve_within("test", "/static/../index.html", false).unwrap();
+    ///     assert_eq!(FileExt::build_path(&["test", "index.html"]), path);
+    ///
+    ///     let error = FileExt::resolve_within("test", "../Cargo.toml", false).err().unwrap();
+    ///     assert_eq!(Some(PathRejection::Traversal), error.rejection());
+    /// }
+    /// ```
+    pub fn resolve_within(root: &str, untrusted_path: &str, resolve_symlinks: bool) -> Result<String, FileExtError> {
+        PathExtImpl::resolve_within(root, untrusted_path, resolve_symlinks)
+    }
+
+    /// Will return absolute working directory path appended to the given string.
+    /// Path is not checked, use `resolve_within` for paths which come from untrusted sources
     /// # Examples
     ///
     /// ```
@@ -199,11 +341,11 @@ impl FileExt {
     ///     let path = boxed_path.unwrap();
     /// }
     /// ```
-    pub fn get_static_filepath(path: &str) -> Result<String, String> {
+    pub fn get_static_filepath(path: &str) -> Result<String, FileExtError> {
         let boxed_working_directory = PathExtImpl::absolute_path_to_working_directory();
         if boxed_working_directory.is_err() {
-            let message = boxed_working_directory.err().unwrap();
-            return Err(message)
+            let error = boxed_working_directory.err().unwrap();
+            return Err(error)
         }
 
         let working_directory = boxed_working_directory.unwrap();
@@ -242,7 +384,7 @@ impl FileExt {
     ///      assert!(doesnt_exist);
     ///  }
     /// ```
-    pub fn read_or_create_and_write(path: &str, content: &[u8]) -> Result<Vec<u8>, String> {
+    pub fn read_or_create_and_write(path: &str, content: &[u8]) -> Result<Vec<u8>, FileExtError> {
         FileExtImpl::read_or_create_and_write(path, content)
     }
 
@@ -269,7 +411,7 @@ impl FileExt {
     ///     assert!(!exists);
     /// }
     /// ```
-    pub fn create_file(path: &str) -> Result<(), String>  {
+    pub fn create_file(path: &str) -> Result<(), FileExtError>  {
         FileExtImpl::create_file(path)
     }
 
@@ -324,10 +466,35 @@ impl FileExt {
     ///      assert!(boxed_delete.is_ok());
     ///  }
     /// ```
-    pub fn create_directory(path: &str) -> Result<(), String> {
+    pub fn create_directory(path: &str) -> Result<(), FileExtError> {
         DirectoryExtImpl::create_directory(path)
     }
 
+    /// Creates the directory with all missing parents (`mkdir -p`), succeeds if the directory already exists.
+    /// On Unix `mode` (for example `0o750`) is applied to newly created directories, on other platforms it is ignored.
+    /// Returns paths of created directories, outermost first, so they can be removed later. If creation fails,
+    /// directories created by the call are removed.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::FileExt;
+    ///  #[test]
+    ///  fn ensure_directory() {
+    ///      let path = FileExt::build_path(&["ensure_directory", "nested"]);
+    ///
+    ///      let created_list = FileExt::ensure_directory(&path, None).unwrap();
+    ///      assert_eq!(vec!["ensure_directory".to_string(), path.clone()], created_list);
+    ///
+    ///      let created_list = FileExt::ensure_directory(&path, None).unwrap();
+    ///      assert!(created_list.is_empty());
+    ///
+    ///      FileExt::delete_directory("ensure_directory").unwrap();
+    ///  }
+    /// ```
+    pub fn ensure_directory(path: &str, mode: Option<u32>) -> Result<Vec<String>, FileExtError> {
+        DirectoryExtImpl::ensure_directory(path, mode)
+    }
+
     /// Will delete directory and all of the content on specified path (won't follow symlinks)
     /// # Examples
     ///
@@ -346,10 +513,138 @@ impl FileExt {
     ///      assert!(boxed_delete.is_ok());
     ///  }
     /// ```
-    pub fn delete_directory(path: &str) -> Result<(), String> {
+    pub fn delete_directory(path: &str) -> Result<(), FileExtError> {
         DirectoryExtImpl::delete_directory(path)
     }
 
+    /// Deletes directory with all of its content. Symlinks are removed, not followed, so nothing outside of the tree is deleted.
+    /// If the path itself is a symlink to a directory, only the symlink is removed.
+    /// If `options.continue_on_error` is set, entries which can not be deleted are collected in the report instead of failing.
+    /// If `options.staging_directory` is set, directory is moved there first and deleted afterwards,
+    /// staging directory has to exist outside of the deleted tree.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::{DeleteDirectoryOptions, FileExt};
+    ///  #[test]
+    ///  fn delete_directory_with_options() {
+    ///      let path = "delete_directory_with_options";
+    ///      FileExt::create_directory(path).unwrap();
+    ///      FileExt::create_file("delete_directory_with_options/file.txt").unwrap();
+    ///
+    ///      let options = DeleteDirectoryOptions { continue_on_error: true, ..DeleteDirectoryOptions::default() };
+    ///      let report = FileExt::delete_directory_with_options(path, &options).unwrap();
+    ///
+    ///      assert!(report.is_complete());
+    ///      assert_eq!(2, report.deleted_count);
+    ///      assert!(!FileExt::does_directory_exist(path));
+    ///  }
+    /// ```
+    pub fn delete_directory_with_options(path: &str, options: &DeleteDirectoryOptions) -> Result<DeleteDirectoryReport, FileExtError> {
+        DirectoryExtImpl::delete_directory_with_options(path, options)
+    }
+
+
+    /// Returns entries of the directory with their metadata, sorted by name. Symlinks are not followed.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::{EntryKind, FileExt};
+    ///  #[test]
+    ///  fn list_directory() {
+    ///      let entry_list = FileExt::list_directory("test").unwrap();
+    ///
+    ///      let entry = entry_list.iter().find(|entry| entry.name == "index.html").unwrap();
+    ///      assert_eq!(EntryKind::File, entry.kind);
+    ///      assert!(entry.size > 0);
+    ///  }
+    /// ```
+    pub fn list_directory(path: &str) -> Result<Vec<DirectoryEntry>, FileExtError> {
+        DirectoryExtImpl::list_directory(path, &ListDirectoryOptions::default())
+    }
+
+    /// Same as `list_directory`, entries are sorted and symlinks are followed according to the options
+    pub fn list_directory_with_options(path: &str, options: &ListDirectoryOptions) -> Result<Vec<DirectoryEntry>, FileExtError> {
+        DirectoryExtImpl::list_directory(path, options)
+    }
+
+    /// Recursively walks the directory tree, up to `options.max_depth` levels deep.
+    /// Directory is listed right before its content. If `options.follow_symlinks` is set,
+    /// walker descends into directory symlinks and reports symlink loops as errors.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::{FileExt, ListDirectoryOptions};
+    ///  #[test]
+    ///  fn walk_directory() {
+    ///      let options = ListDirectoryOptions { max_depth: Some(2), ..ListDirectoryOptions::default() };
+    ///      for boxed_entry in FileExt::walk_directory("test", &options).unwrap() {
+    ///          let entry = boxed_entry.unwrap();
+    ///          assert!(entry.depth <= 2);
+    ///      }
+    ///  }
+    /// ```
+    pub fn walk_directory(path: &str, options: &ListDirectoryOptions) -> Result<DirectoryWalker, FileExtError> {
+        DirectoryExtImpl::walk_directory(path, options)
+    }
+
+    /// Lazily finds files, directories and symlinks matching the glob pattern, see `GlobPattern` for the syntax.
+    /// Relative patterns are resolved against the working directory.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::FileExt;
+    ///  #[test]
+    ///  fn glob() {
+    ///      let path_list : Vec<String> = FileExt::glob("test/**/*.html").unwrap()
+    ///          .map(|boxed_entry| boxed_entry.unwrap().path)
+    ///          .collect();
+    ///
+    ///      assert!(path_list.contains(&FileExt::build_path(&["test", "index.html"])));
+    ///  }
+    /// ```
+    pub fn glob(pattern: &str) -> Result<GlobWalker, FileExtError> {
+        let boxed_pattern = GlobPattern::new(pattern);
+        if boxed_pattern.is_err() {
+            let error = boxed_pattern.err().unwrap();
+            return Err(error)
+        }
+
+        Ok(GlobWalker::new(boxed_pattern.unwrap()))
+    }
+
+    /// Copies directory with all of its content. Destination directory is created if it does not exist.
+    /// Files are copied block by block, callbacks receive progress aggregated over the whole tree:
+    /// first and last byte of the block and the total size of all files.
+    /// If cancel callback returns true, copying stops and `CopyDirectoryStatus::Cancelled` is returned.
+    /// Symlinks are handled according to `options.symlink_policy`.
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::{CopyDirectoryOptions, CopyDirectoryStatus, FileExt};
+    ///  #[test]
+    ///  fn copy_directory() {
+    ///      let options = CopyDirectoryOptions::default();
+    ///      let status = FileExt::copy_directory("test", "test_copy", &options, |_start, _end, _total| {}, |_start, _end, _total| false).unwrap();
+    ///      assert!(matches!(status, CopyDirectoryStatus::Completed { .. }));
+    ///
+    ///      assert!(FileExt::does_file_exist("test_copy/index.html"));
+    ///
+    ///      FileExt::delete_directory("test_copy").unwrap();
+    ///  }
+    /// ```
+    pub fn copy_directory
+    <F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: &str,
+        to: &str,
+        options: &CopyDirectoryOptions,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<CopyDirectoryStatus, FileExtError> {
+        DirectoryExtImpl::copy_directory(from, to, options, progress_callback, cancel_callback)
+    }
 
     /// Returns boolean indicating symlink existence on the path
     /// # Examples
@@ -407,7 +702,7 @@ impl FileExt {
     ///    assert_eq!(expected_path, actual_path);
     /// }
     /// ```
-    pub fn resolve_symlink_path(symlink_directory: &str, symlink_points_to: &str) -> Result<String, String> {
+    pub fn resolve_symlink_path(symlink_directory: &str, symlink_points_to: &str) -> Result<String, FileExtError> {
         SymlinkExtImpl::resolve_symlink_path(symlink_directory, symlink_points_to)
     }
 
@@ -428,10 +723,54 @@ impl FileExt {
     ///
     ///  }
     /// ```
-    pub fn write_file(path: &str, file_content: &[u8]) -> Result<(), String> {
+    pub fn write_file(path: &str, file_content: &[u8]) -> Result<(), FileExtError> {
         FileExtImpl::write_file(path, file_content)
     }
 
+    /// Will write given byte array to a file on the path. `WriteMode` specifies whether file is created,
+    /// appended or overwritten.
+    /// # Examples
+    /// ```
+    ///  use file_ext::{FileExt, WriteMode};
+    /// #[test]
+    ///  fn write_with_mode() {
+    ///      let filename = "write-with-mode-test.content";
+    ///      FileExt::write_file_with_mode(filename, "data".as_bytes(), WriteMode::CreateNew).unwrap();
+    ///      FileExt::write_file_with_mode(filename, "new data".as_bytes(), WriteMode::Overwrite).unwrap();
+    ///
+    ///      let actual = FileExt::read_file(filename).unwrap();
+    ///      assert_eq!(actual, "new data".as_bytes());
+    ///
+    ///      FileExt::delete_file(filename).unwrap();
+    ///  }
+    /// ```
+    pub fn write_file_with_mode(path: &str, file_content: &[u8], mode: WriteMode) -> Result<(), FileExtError> {
+        FileExtImpl::write_file_with_mode(path, file_content, mode)
+    }
+
+    /// Will replace content of the file on the path with the given byte array in a single step.
+    /// Content is written to a temporary file in the same directory, synced to disk and then renamed over the target,
+    /// so readers either see the old content or the new one. Permissions of the existing file are preserved.
+    /// If the path is a symlink, the file it points to is replaced and the link is kept.
+    /// # Examples
+    /// ```
+    ///  use file_ext::FileExt;
+    /// #[test]
+    ///  fn write_atomically() {
+    ///      let filename = "write-atomically-test.content";
+    ///      FileExt::write_file_atomically(filename, "version 1".as_bytes()).unwrap();
+    ///      FileExt::write_file_atomically(filename, "version 2".as_bytes()).unwrap();
+    ///
+    ///      let actual = FileExt::read_file(filename).unwrap();
+    ///      assert_eq!(actual, "version 2".as_bytes());
+    ///
+    ///      FileExt::delete_file(filename).unwrap();
+    ///  }
+    /// ```
+    pub fn write_file_atomically(path: &str, file_content: &[u8]) -> Result<(), FileExtError> {
+        FileExtImpl::write_file_atomically(path, file_content)
+    }
+
     /// Will delete file on a given path
     /// # Examples
     ///
@@ -455,7 +794,7 @@ impl FileExt {
     ///     assert!(!exists);
     /// }
     /// ```
-    pub fn delete_file(path: &str) -> Result<(), String> {
+    pub fn delete_file(path: &str) -> Result<(), FileExtError> {
         FileExtImpl::delete_file(path)
     }
 
@@ -492,7 +831,7 @@ impl FileExt {
     ///}
     ///```
     #[cfg(target_family = "unix")]
-    pub fn create_symlink(symlink_path: &str, symlink_name: &str, symlink_points_to: &str) -> Result<(), String> {
+    pub fn create_symlink(symlink_path: &str, symlink_name: &str, symlink_points_to: &str) -> Result<(), FileExtError> {
         SymlinkExtImpl::create_symlink(symlink_path, symlink_name, symlink_points_to)
     }
 
@@ -530,10 +869,113 @@ impl FileExt {
     ///}
     ///```
     #[cfg(target_family = "windows")]
-    pub fn create_symlink(symlink_path: &str, symlink_name: &str, symlink_points_to: &str) -> Result<(), String> {
+    pub fn create_symlink(symlink_path: &str, symlink_name: &str, symlink_points_to: &str) -> Result<(), FileExtError> {
         SymlinkExtImpl::create_symlink(symlink_path, symlink_name, symlink_points_to)
     }
 
+    /// Creates symlink `symlink_name` in the directory `symlink_path` pointing to `symlink_points_to`.
+    /// Relative target is resolved against `symlink_path` and has to exist. With `keep_relative_target`
+    /// the link stores the target text as given, so a relocatable directory tree can be moved as a whole.
+    /// `kind` requires the target to be a file or a directory, on Windows it also selects the type of symlink
+    /// # Examples
+    /// ```
+    /// use file_ext::{FileExt, SymlinkOptions};
+    /// #[test]
+    /// fn relative_symlink() {
+    ///     let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    ///     FileExt::create_symlink_with_options("test", "index-relative-link", "index.html", &options).unwrap();
+    ///
+    ///     let symlink_path = FileExt::build_path(&["test", "index-relative-link"]);
+    ///     assert_eq!("index.html", FileExt::symlink_points_to(&symlink_path).unwrap());
+    ///
+    ///     FileExt::delete_file(&symlink_path).unwrap();
+    /// }
+    /// ```
+    #[cfg(any(target_family = "unix", target_family = "windows"))]
+    pub fn create_symlink_with_options(symlink_path: &str, symlink_name: &str, symlink_points_to: &str, options: &SymlinkOptions) -> Result<(), FileExtError> {
+        SymlinkExtImpl::create_symlink_with_options(symlink_path, symlink_name, symlink_points_to, options)
+    }
+
+    /// Points existing symlink `link` to `new_target` atomically: a temp link is created next to it and renamed over it,
+    /// so readers see either the old or the new target, never a missing link. Target is stored as given, relative target
+    /// is resolved against the directory of the link and has to exist. Returns the previous target.
+    /// Rename is atomic, so a failed switch leaves the old link untouched and there is nothing to roll back.
+    /// Once the rename succeeded the previous target is returned, even if syncing the directory afterwards fails:
+    /// the link already points to the new target and only durability across a power loss is not guaranteed.
+    /// Available on Unix only, rename on Windows can not replace a directory symlink
+    /// # Examples
+    /// ```
+    /// use file_ext::{FileExt, SymlinkOptions};
+    /// #[test]
+    /// fn blue_green_switch() {
+    ///     let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    ///     FileExt::create_symlink_with_options(".", "current", "test", &options).unwrap();
+    ///
+    ///     let previous_target = FileExt::replace_symlink("current", "src").unwrap();
+    ///     assert_eq!("test", previous_target);
+    ///     assert_eq!("src", FileExt::symlink_points_to("current").unwrap());
+    ///
+    ///     FileExt::delete_file("current").unwrap();
+    /// }
+    /// ```
+    #[cfg(target_family = "unix")]
+    pub fn replace_symlink(link: &str, new_target: &str) -> Result<String, FileExtError> {
+        SymlinkExtImpl::replace_symlink(link, new_target)
+    }
+
+    /// Creates hard link `new_path` to the file `existing_path`, both names refer to the same content afterwards.
+    /// Fails if something already exists at `new_path`, if `existing_path` does not exist, is a directory or a symlink
+    /// # Examples
+    /// ```
+    /// use file_ext::FileExt;
+    /// #[test]
+    /// fn deduplicate_asset() {
+    ///     FileExt::create_hard_link("test/index.html", "test/index-copy.html").unwrap();
+    ///     assert_eq!(FileExt::read_file("test/index.html").unwrap(), FileExt::read_file("test/index-copy.html").unwrap());
+    ///
+    ///     FileExt::delete_file("test/index-copy.html").unwrap();
+    /// }
+    /// ```
+    pub fn create_hard_link(existing_path: &str, new_path: &str) -> Result<(), FileExtError> {
+        HardLinkExtImpl::create_hard_link(existing_path, new_path)
+    }
+
+    /// Returns number of hard links (names) the file has, symlinks are followed.
+    /// Available on Unix only, the link count is not exposed by the standard library on Windows
+    /// # Examples
+    /// ```
+    /// use file_ext::FileExt;
+    /// #[test]
+    /// fn link_count() {
+    ///     FileExt::create_hard_link("test/index.html", "test/index-copy.html").unwrap();
+    ///     assert_eq!(2, FileExt::hard_link_count("test/index.html").unwrap());
+    ///
+    ///     FileExt::delete_file("test/index-copy.html").unwrap();
+    /// }
+    /// ```
+    #[cfg(target_family = "unix")]
+    pub fn hard_link_count(path: &str) -> Result<u64, FileExtError> {
+        HardLinkExtImpl::hard_link_count(path)
+    }
+
+    /// Checks if both paths refer to the same file by comparing device and inode, symlinks are followed.
+    /// Available on Unix only, the file index is not exposed by the standard library on Windows
+    /// # Examples
+    /// ```
+    /// use file_ext::FileExt;
+    /// #[test]
+    /// fn same_file() {
+    ///     FileExt::create_hard_link("test/index.html", "test/index-copy.html").unwrap();
+    ///     assert!(FileExt::are_same_file("test/index.html", "test/index-copy.html").unwrap());
+    ///
+    ///     FileExt::delete_file("test/index-copy.html").unwrap();
+    /// }
+    /// ```
+    #[cfg(target_family = "unix")]
+    pub fn are_same_file(path: &str, other_path: &str) -> Result<bool, FileExtError> {
+        HardLinkExtImpl::are_same_file(path, other_path)
+    }
+
     /// Checks if the file is symlink
     /// # Examples
     ///
@@ -546,10 +988,37 @@ impl FileExt {
     ///     assert!(is_symlink);
     /// }
     /// ```
-    pub fn is_symlink(path: &str) -> Result<bool, String> {
+    pub fn is_symlink(path: &str) -> Result<bool, FileExtError> {
         SymlinkExtImpl::is_symlink(path)
     }
 
+    /// Checks if the path is a symlink pointing to something that does not exist.
+    /// Relative target is resolved against the directory of the symlink, regular files and directories are not dangling
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::{FileExt, SymlinkOptions};
+    /// #[test]
+    /// fn dangling_link() {
+    ///     let options = SymlinkOptions { keep_relative_target: true, allow_dangling: true, ..Default::default() };
+    ///     FileExt::create_symlink_with_options("test", "not-yet-deployed", "release", &options).unwrap();
+    ///
+    ///     let path = FileExt::build_path(&["test", "not-yet-deployed"]);
+    ///     assert!(FileExt::is_dangling(&path).unwrap());
+    ///
+    ///     FileExt::delete_file(&path).unwrap();
+    /// }
+    /// ```
+    pub fn is_dangling(path: &str) -> Result<bool, FileExtError> {
+        SymlinkExtImpl::is_dangling(path)
+    }
+
+    /// Returns paths of all dangling symlinks in the directory tree. Symlinks to directories are reported
+    /// if they are broken, but never followed
+    pub fn find_broken_symlinks(directory: &str) -> Result<Vec<String>, FileExtError> {
+        SymlinkExtImpl::find_broken_symlinks(directory)
+    }
+
     /// Returns path to a file, symlink points to
     /// # Examples
     ///
@@ -562,10 +1031,29 @@ impl FileExt {
     ///     assert_eq!("index.html", points_to);
     /// }
     /// ```
-    pub fn symlink_points_to(path: &str) -> Result<String, String> {
+    pub fn symlink_points_to(path: &str) -> Result<String, FileExtError> {
         SymlinkExtImpl::symlink_points_to(path)
     }
 
+    /// Follows symlink through all of its hops. Returns the given path followed by the target of every hop
+    /// and the canonical path of the final target. Fails with `InvalidData` if a loop is detected or more than
+    /// `SymlinkChain::MAX_DEPTH` (40, as the Linux `ELOOP` limit) symlinks have to be followed, and with `NotFound` if the chain is broken
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::FileExt;
+    /// #[test]
+    /// fn symlink_chain() {
+    ///     let path = FileExt::build_path(&["test", "index_rewrite"]);
+    ///     let chain = FileExt::resolve_symlink_chain(&path).unwrap();
+    ///     assert_eq!(2, chain.hop_list.len());
+    ///     assert!(chain.target.ends_with("index.html"));
+    /// }
+    /// ```
+    pub fn resolve_symlink_chain(path: &str) -> Result<SymlinkChain, FileExtError> {
+        SymlinkExtImpl::resolve_symlink_chain(path)
+    }
+
     /// Builds a path from a given node list
     /// # Examples
     ///
@@ -628,6 +1116,29 @@ impl FileExt {
         PathExtImpl::build_path(list)
     }
 
+    /// Collapses `.`, `..` and repeated separators in the path, using path syntax of the current platform.
+    /// See `PathStyle` for parent, file name, extension and other path string functions.
+    /// # Examples
+    ///
+    /// ```
+    /// use file_ext::FileExt;
+    /// #[test]
+    /// #[cfg(target_family = "unix")]
+    /// fn normalize_path() {
+    ///     assert_eq!("/home/user/file.txt", FileExt::normalize_path("/home//user/./folder/../file.txt"));
+    ///     assert_eq!("../file.txt", FileExt::normalize_path("folder/../../file.txt"));
+    /// }
+    /// ```
+    pub fn normalize_path(path: &str) -> String {
+        PathStyle::native().normalize(path)
+    }
+
+    /// Returns path relative to the base, for example `../b/c` for path `a/b/c` and base `a/d`.
+    /// None if one of the paths is absolute and the other is not, or if they are on different drives
+    pub fn relative_path(path: &str, base: &str) -> Option<String> {
+        PathStyle::native().relative_to(path, base)
+    }
+
     /// Root node of the system. It is meant to be used in `build_path` function.
     /// On Linux and macOS `build_path` function will evaluate it to `/`,
     /// on Windows it will be `C:`
@@ -641,13 +1152,12 @@ impl FileExt {
         PathExtImpl::folder_up()
     }
 
-    /// Returns name of the user running the process
+    /// Returns name of the user running the process, see `get_current_user_info`
     /// # Examples
     ///
     /// ```
     ///  use file_ext::FileExt;
     ///  #[test]
-    ///  #[cfg(target_family = "unix")]
     ///  fn current_user() {
     ///      let boxed_user = FileExt::get_current_user();
     ///      assert!(boxed_user.is_ok());
@@ -655,28 +1165,25 @@ impl FileExt {
     ///      let user = boxed_user.unwrap();
     ///  }
     /// ```
-    #[cfg(target_family = "unix")]
-    pub fn get_current_user() -> Result<String, String> {
+    pub fn get_current_user() -> Result<String, FileExtError> {
         UserExtImpl::get_current_user()
     }
 
-    /// Returns name of the user running the process
+    /// Returns name, uid, gid, home directory and shell of the user running the process without spawning
+    /// any process. On Unix `/etc/passwd` entry of the effective user is used, with `USER`/`LOGNAME`, `HOME`
+    /// and `SHELL` as fallbacks. On Windows values come from `USERNAME`, `USERPROFILE` and `COMSPEC`
     /// # Examples
     ///
     /// ```
     ///  use file_ext::FileExt;
     ///  #[test]
-    ///  #[cfg(target_family = "windows")]
-    ///  fn current_user() {
-    ///      let boxed_user = FileExt::get_current_user();
-    ///      assert!(boxed_user.is_ok());
-    ///
-    ///      let user = boxed_user.unwrap();
+    ///  fn current_user_info() {
+    ///      let user_info = FileExt::get_current_user_info().unwrap();
+    ///      assert_eq!(FileExt::get_current_user().unwrap(), user_info.name);
     ///  }
     /// ```
-    #[cfg(target_family = "windows")]
-    pub fn get_current_user() -> Result<String, String> {
-        UserExtImpl::get_current_user()
+    pub fn get_current_user_info() -> Result<UserInfo, FileExtError> {
+        UserExtImpl::get_current_user_info()
     }
 
     /// Returns domain of the user running the process
@@ -694,43 +1201,66 @@ impl FileExt {
     ///  }
     /// ```
     #[cfg(target_family = "windows")]
-    pub fn get_current_user_domain() -> Result<String, String> {
+    pub fn get_current_user_domain() -> Result<String, FileExtError> {
         UserExtImpl::get_current_user_domain()
     }
 
-    /// Returns path to the temporary folder
+    /// Returns path to the temporary folder, honoring the environment the way the platform does:
+    /// `TMPDIR` on Unix (`/tmp` if it is not set), `TMP`, `TEMP` or user profile on Windows
     /// # Examples
     ///
     /// ```
     ///  use file_ext::FileExt;
     ///  #[test]
-    ///  #[cfg(target_family = "windows")]
     ///  fn temp_folder() {
     ///      let temp_folder_path = FileExt::get_temp_folder_path().unwrap();
-    ///      assert!(temp_folder_path.starts_with("C:\\Users\\"));
-    ///      assert!(temp_folder_path.ends_with("\\AppData\\Local\\Temp"));
+    ///      assert!(FileExt::does_directory_exist(&temp_folder_path));
     ///  }
     /// ```
-    #[cfg(target_family = "windows")]
-    pub fn get_temp_folder_path() -> Result<String, String>{
+    pub fn get_temp_folder_path() -> Result<String, FileExtError>{
         PathExtImpl::get_temp_folder_path()
     }
 
-    /// Returns path to the temporary folder
+    /// Creates a new empty file named `<prefix><random><suffix>` in the temporary folder.
+    /// File is created exclusively (`O_EXCL`), on Unix it is readable and writable only by the owner (0600).
+    /// Returned guard deletes the file when dropped, call `keep` to leave it on disk
     /// # Examples
     ///
     /// ```
     ///  use file_ext::FileExt;
     ///  #[test]
-    ///  #[cfg(target_family = "unix")]
-    ///  fn temp_folder() {
-    ///      let temp_folder_path = FileExt::get_temp_folder_path().unwrap();
-    ///      assert_eq!(temp_folder_path, "/tmp")
+    ///  fn temp_file() {
+    ///      let path = {
+    ///          let temp_file = FileExt::create_temp_file("upload-", ".part").unwrap();
+    ///          FileExt::write_file(temp_file.path(), "data".as_bytes()).unwrap();
+    ///          assert!(temp_file.path().ends_with(".part"));
+    ///          temp_file.path().to_string()
+    ///      };
+    ///      assert!(!FileExt::does_file_exist(&path));
     ///  }
     /// ```
-    #[cfg(any(target_family = "unix", target_family = "wasm"))]
-    pub fn get_temp_folder_path() -> Result<String, String>{
-        PathExtImpl::get_temp_folder_path()
+    pub fn create_temp_file(prefix: &str, suffix: &str) -> Result<TempFile, FileExtError> {
+        FileExtImpl::create_temp_file(prefix, suffix)
+    }
+
+    /// Creates a new directory named `<prefix><random>` in the temporary folder, on Unix it is accessible only by the owner (0700).
+    /// Returned guard deletes the directory with all of its content when dropped, call `keep` to leave it on disk
+    /// # Examples
+    ///
+    /// ```
+    ///  use file_ext::FileExt;
+    ///  #[test]
+    ///  fn temp_directory() {
+    ///      let temp_directory = FileExt::create_temp_directory("build-").unwrap();
+    ///      let path = FileExt::build_path(&[temp_directory.path(), "output.txt"]);
+    ///      FileExt::create_file(&path).unwrap();
+    ///
+    ///      drop(temp_directory);
+    ///      assert!(!FileExt::does_file_exist(&path));
+    ///  }
+    /// ```
+    pub fn create_temp_directory(prefix: &str) -> Result<TempDirectory, FileExtError> {
+        DirectoryExtImpl::create_temp_directory(prefix)
     }
 
     /// Returns file length in bytes
@@ -745,7 +1275,7 @@ impl FileExt {
     ///    assert_eq!(expected_length, length);
     /// }
     /// ```
-    pub fn file_length(path: Vec<&str>) -> Result<u64, String> {
+    pub fn file_length(path: Vec<&str>) -> Result<u64, FileExtError> {
         FileExtImpl::file_length(path)
     }
 
@@ -761,13 +1291,14 @@ impl FileExt {
     ///     FileExt::delete_file(path.as_str()).unwrap();
     /// }
     /// ```
-    pub fn copy_file(from: Vec<&str>, to: Vec<&str>)-> Result<(), String> {
+    pub fn copy_file(from: Vec<&str>, to: Vec<&str>)-> Result<(), FileExtError> {
         FileExtImpl::copy_file(from, to)
     }
 
     /// Copies file block by block. If block size is None it is set to 100kb.
     /// Calls the progress callback at the beginning of the block copy.
     /// Calls the cancel callback at the end of the block copy.
+    /// Callbacks receive first and last byte of the block and the file length.
     ///```
     /// use file_ext::FileExt;
     /// #[test]
@@ -797,7 +1328,7 @@ impl FileExt {
                     progress_callback: F,
                     cancel_callback: C
                 )
-                    -> Result<(), String> {
+                    -> Result<(), FileExtError> {
         FileExtImpl::copy_file_with_callbacks(from, to, block_size, progress_callback, cancel_callback)
     }
 
@@ -837,11 +1368,11 @@ impl FileExt {
         progress_callback: F,
         cancel_callback: C,
     )
-        -> Result<(), String> {
+        -> Result<(), FileExtError> {
         FileExtImpl::copy_file_with_callbacks_starting_from_byte(from, to, starting_byte, block_size, progress_callback, cancel_callback)
     }
 
-    /// Copies file block by block starting from specific byte up to ending byte.
+    /// Copies file block by block starting from specific byte up to ending byte, ending byte is copied as well.
     /// If block size is None it is set to 100kb.
     /// Calls the progress callback at the beginning of the block copy.
     /// Calls the cancel callback at the end of the block copy.
@@ -881,9 +1412,100 @@ impl FileExt {
         progress_callback: F,
         cancel_callback: C,
     )
-        -> Result<(), String>
+        -> Result<(), FileExtError>
     {
         FileExtImpl::copy_file_with_callbacks_starting_from_byte_and_ending_at_byte(from, to, starting_byte, ending_byte, block_size, progress_callback, cancel_callback)
     }
+
+    /// Copies given range of the file block by block. Range is half-open: `start` is copied, `end` is not.
+    /// If block size is None it is set to 100kb.
+    /// Calls the progress callback at the beginning of the block copy.
+    /// Calls the cancel callback at the end of the block copy.
+    /// Callbacks receive first and last byte of the block and the end of the range.
+    ///```
+    /// use file_ext::{FileExt, FileRange};
+    /// #[test]
+    /// fn copy_file_range() {
+    ///     let pwd = FileExt::working_directory().unwrap();
+    ///     let range = FileRange::new(4, 11).unwrap();
+    ///     FileExt::copy_file_range_with_callbacks(
+    ///         vec![pwd.as_str(), "LICENSE"],
+    ///         vec![pwd.as_str(), "LICENSE_copy6"],
+    ///         range,
+    ///         None,
+    ///         |_start, _end, _total| {},
+    ///         |_start, _end, _total| false
+    ///     ).unwrap();
+    ///
+    ///     let path = FileExt::build_path(vec![pwd.as_str(), "LICENSE_copy6"].as_slice());
+    ///     assert_eq!(7, FileExt::read_file(path.as_str()).unwrap().len());
+    ///     FileExt::delete_file(path.as_str()).unwrap();
+    /// }
+    ///```
+    pub fn copy_file_range_with_callbacks
+    <F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: Vec<&str>,
+        to: Vec<&str>,
+        range: FileRange,
+        block_size: Option<u64>,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<(), FileExtError>
+    {
+        FileExtImpl::copy_file_range_with_callbacks(from, to, range, block_size, progress_callback, cancel_callback)
+    }
+
+    /// Copies file block by block and records progress in a checkpoint file next to the destination (`<to>.checkpoint`).
+    /// If copy was cancelled or the process crashed, calling the function again continues from the last checkpoint.
+    /// Already copied part of the destination is verified against the checkpoint hash, if source file changed
+    /// (path, size or modification time) or destination does not match, copy starts from the beginning.
+    /// Checkpoint is saved every 8 MiB or 2 seconds, whichever comes first, and when the copy is cancelled.
+    /// Existing destination without a checkpoint is not overwritten unless `overwrite` is true, `AlreadyExists` is returned.
+    /// If block size is None it is set to 100kb. Callbacks are the same as in `copy_file_with_callbacks`.
+    ///```
+    /// use file_ext::{FileExt, ResumableCopyStatus};
+    /// #[test]
+    /// fn copy_file_resumable() {
+    ///     let pwd = FileExt::working_directory().unwrap();
+    ///     let status = FileExt::copy_file_resumable(
+    ///         vec![pwd.as_str(), "LICENSE"],
+    ///         vec![pwd.as_str(), "LICENSE_copy7"],
+    ///         Some(100),
+    ///         false,
+    ///         |_start, _end, _total| {},
+    ///         |_start, _end, _total| true
+    ///     ).unwrap();
+    ///     assert_eq!(ResumableCopyStatus::Cancelled { bytes_copied: 100 }, status);
+    ///
+    ///     let status = FileExt::copy_file_resumable(
+    ///         vec![pwd.as_str(), "LICENSE"],
+    ///         vec![pwd.as_str(), "LICENSE_copy7"],
+    ///         Some(100),
+    ///         false,
+    ///         |_start, _end, _total| {},
+    ///         |_start, _end, _total| false
+    ///     ).unwrap();
+    ///     assert!(matches!(status, ResumableCopyStatus::Completed { resumed_from: 100, .. }));
+    ///
+    ///     let path = FileExt::build_path(vec![pwd.as_str(), "LICENSE_copy7"].as_slice());
+    ///     FileExt::delete_file(path.as_str()).unwrap();
+    /// }
+    ///```
+    pub fn copy_file_resumable
+    <F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: Vec<&str>,
+        to: Vec<&str>,
+        block_size: Option<u64>,
+        overwrite: bool,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<ResumableCopyStatus, FileExtError>
+    {
+        ResumableCopyImpl::copy_file_resumable(from, to, block_size, overwrite, progress_callback, cancel_callback)
+    }
 }
 
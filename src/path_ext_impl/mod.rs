@@ -1,9 +1,11 @@
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::io::ErrorKind;
+use crate::file_ext_error::{FileExtError, Operation, PathRejection};
+use crate::path_style::PathStyle;
 use crate::symbol::SYMBOL;
 
-#[cfg(target_family = "windows")]
-use crate::user_ext_impl::UserExtImpl;
-
 #[cfg(test)]
 mod tests;
 
@@ -25,7 +27,7 @@ impl PathExtImpl {
     }
 
     pub fn build_path(list: &[&str]) -> String {
-        list.join(PathExtImpl::get_path_separator().as_str())
+        PathStyle::native().join(list)
     }
 
     #[cfg(target_family = "windows")]
@@ -38,47 +40,135 @@ impl PathExtImpl {
         SYMBOL.reverse_slash.to_string()
     }
 
-    #[cfg(any(target_family = "unix", target_family = "wasm"))]
-    pub fn get_temp_folder_path() -> Result<String, String>{
-        Ok("/tmp".to_string())
+    /// Returns temporary folder the way the platform does: `TMPDIR` on Unix (`/tmp` if not set),
+    /// `TMP`, `TEMP` or `USERPROFILE` on Windows. Trailing separator is removed
+    pub fn get_temp_folder_path() -> Result<String, FileExtError>{
+        let temp_dir = env::temp_dir();
+        let boxed_temp_folder = temp_dir.to_str();
+        if boxed_temp_folder.is_none() {
+            let message = "temp folder path is not valid UTF-8".to_string();
+            return Err(FileExtError::from_kind(Operation::TempFolder, &temp_dir.to_string_lossy(), ErrorKind::InvalidData, message))
+        }
+
+        let temp_folder = boxed_temp_folder.unwrap();
+        let separator = PathStyle::native().separator();
+        let trimmed = temp_folder.trim_end_matches(['/', separator]);
+        // root and drive root keep their separator
+        if trimmed.is_empty() || trimmed.ends_with(':') {
+            return Ok(temp_folder.to_string())
+        }
+        Ok(trimmed.to_string())
     }
 
-    #[cfg(target_family = "windows")]
-    pub fn get_temp_folder_path() -> Result<String, String>{
-        let boxed_username = UserExtImpl::get_current_user();
-        if boxed_username.is_err() {
-            let message = boxed_username.err().unwrap().to_string();
-            return Err(message)
+    /// Same as `get_temp_folder_path`, path is returned as given by the platform
+    pub fn temp_folder_path() -> PathBuf {
+        env::temp_dir()
+    }
+
+    pub fn resolve_within(root: &str, untrusted_path: &str, resolve_symlinks: bool) -> Result<String, FileExtError> {
+        let boxed_path = PathExtImpl::resolve_within_path(root, untrusted_path, resolve_symlinks);
+        if boxed_path.is_err() {
+            let error = boxed_path.err().unwrap();
+            return Err(error)
+        }
+        Ok(boxed_path.unwrap().to_string_lossy().to_string())
+    }
+
+    /// Same as `resolve_within`, root and the result are OS paths. Untrusted path comes from a request, so it is a string
+    pub fn resolve_within_path<P: AsRef<Path>>(root: P, untrusted_path: &str, resolve_symlinks: bool) -> Result<PathBuf, FileExtError> {
+        let root = root.as_ref();
+        if untrusted_path.contains('\0') {
+            return Err(FileExtError::outside_root(untrusted_path, PathRejection::InvalidCharacter))
+        }
+
+        let style = PathStyle::native();
+        // request paths usually start with a separator, they are still relative to the root
+        let relative_path = untrusted_path.trim_start_matches(|symbol| symbol == '/' || symbol == style.separator());
+        if style.has_prefix(relative_path) {
+            return Err(FileExtError::outside_root(untrusted_path, PathRejection::AbsolutePath))
+        }
+
+        let normalized = style.normalize(relative_path);
+        if normalized == ".." || normalized.starts_with(&["..", style.separator().to_string().as_str()].join("")) {
+            return Err(FileExtError::outside_root(untrusted_path, PathRejection::Traversal))
+        }
+
+        let path = if normalized == "." {
+            root.to_path_buf()
+        } else {
+            root.join(normalized)
+        };
+
+        if !resolve_symlinks {
+            return Ok(path)
         }
 
-        let username = boxed_username.unwrap();
-        let path = ["C:", "Users", username.as_str(), "AppData", "Local", "Temp"].join(PathExtImpl::get_path_separator().as_str());
-        Ok(path)
+        let boxed_canonical_root = fs::canonicalize(root);
+        if boxed_canonical_root.is_err() {
+            let error = boxed_canonical_root.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ResolvePath, &root.to_string_lossy(), error))
+        }
+        let canonical_root = boxed_canonical_root.unwrap();
+
+        // path may not exist yet, resolve its longest existing part, the rest can not contain symlinks
+        let mut existing = path.clone();
+        let mut missing_list = vec![];
+        let mut boxed_canonical = fs::canonicalize(&existing);
+        while boxed_canonical.is_err() && fs::symlink_metadata(&existing).is_err() {
+            let boxed_file_name = existing.file_name().map(|file_name| file_name.to_os_string());
+            if boxed_file_name.is_none() || !existing.pop() {
+                break;
+            }
+            missing_list.push(boxed_file_name.unwrap());
+            boxed_canonical = fs::canonicalize(&existing);
+        }
+
+        if boxed_canonical.is_err() {
+            // dangling symlink or broken path component, it can not be proven to stay within the root
+            let error = boxed_canonical.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ResolvePath, &path.to_string_lossy(), error))
+        }
+
+        let mut resolved = boxed_canonical.unwrap();
+        if !resolved.starts_with(&canonical_root) {
+            return Err(FileExtError::outside_root(untrusted_path, PathRejection::SymlinkEscape))
+        }
+
+        for missing in missing_list.iter().rev() {
+            resolved.push(missing);
+        }
+        Ok(resolved)
     }
 
-    pub fn absolute_path_to_working_directory() -> Result<String, String> {
+    pub fn absolute_path_to_working_directory() -> Result<String, FileExtError> {
         PathExtImpl::working_directory()
     }
 
-    pub fn working_directory() -> Result<String, String> {
-        let boxed_dir = env::current_dir();
+    pub fn working_directory() -> Result<String, FileExtError> {
+        let boxed_dir = PathExtImpl::working_directory_path();
         if boxed_dir.is_err() {
             let error = boxed_dir.err().unwrap();
-            eprintln!("{}", error);
-            return Err(error.to_string());
+            return Err(error)
         }
         let dir = boxed_dir.unwrap();
 
-
         let boxed_working_directory = dir.as_path().to_str();
         if boxed_working_directory.is_none() {
-            let error = "working directory is not set";
-            eprintln!("{}", error);
-            return Err(error.to_string());
+            let message = "working directory is not valid UTF-8, use FileExtPath::working_directory to read it".to_string();
+            return Err(FileExtError::from_kind(Operation::WorkingDirectory, &dir.to_string_lossy(), ErrorKind::InvalidData, message));
         }
 
         let working_directory = boxed_working_directory.unwrap();
         Ok(working_directory.to_string())
     }
 
+    pub fn working_directory_path() -> Result<PathBuf, FileExtError> {
+        let boxed_dir = env::current_dir();
+        if boxed_dir.is_err() {
+            let error = boxed_dir.err().unwrap();
+            return Err(FileExtError::from_io(Operation::WorkingDirectory, SYMBOL.empty_string, error));
+        }
+        Ok(boxed_dir.unwrap())
+    }
+
 }
\ No newline at end of file
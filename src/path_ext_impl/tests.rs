@@ -1,18 +1,13 @@
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::{FileExtError, PathRejection};
 use crate::path_ext_impl::PathExtImpl;
 
 #[test]
-#[cfg(target_family = "windows")]
-fn temp_folder() {
-    let temp_folder_path = PathExtImpl::get_temp_folder_path().unwrap();
-    assert!(temp_folder_path.starts_with("C:\\Users\\"));
-    assert!(temp_folder_path.ends_with("\\AppData\\Local\\Temp"));
-}
-
-#[test]
-#[cfg(target_family = "unix")]
 fn temp_folder() {
     let temp_folder_path = PathExtImpl::get_temp_folder_path().unwrap();
-    assert_eq!(temp_folder_path, "/tmp")
+    let expected = std::env::temp_dir();
+    assert_eq!(expected.to_str().unwrap().trim_end_matches(['/', '\\']), temp_folder_path);
+    assert!(DirectoryExtImpl::does_directory_exist(&temp_folder_path));
 }
 
 #[test]
@@ -72,3 +67,69 @@ fn absolute_path_to_working_directory() {
     assert!(boxed_path.is_ok());
     let _path = boxed_path.unwrap();
 }
+
+#[test]
+fn resolve_within() {
+    let separator = PathExtImpl::get_path_separator();
+    let case_list = [
+        ("index.html", Ok(PathExtImpl::build_path(&["test", "index.html"]))),
+        ("/index.html", Ok(PathExtImpl::build_path(&["test", "index.html"]))),
+        ("static//./css/../index.html", Ok(PathExtImpl::build_path(&["test", "static", "index.html"]))),
+        ("", Ok("test".to_string())),
+        ("/", Ok("test".to_string())),
+        ("a/..", Ok("test".to_string())),
+        ("..", Err(PathRejection::Traversal)),
+        ("../Cargo.toml", Err(PathRejection::Traversal)),
+        ("/../Cargo.toml", Err(PathRejection::Traversal)),
+        ("a/../../Cargo.toml", Err(PathRejection::Traversal)),
+        ("index.html\0.txt", Err(PathRejection::InvalidCharacter)),
+    ];
+
+    for (untrusted_path, expected) in case_list {
+        let actual = PathExtImpl::resolve_within("test", untrusted_path, false).map_err(|error| error.rejection().unwrap());
+        assert_eq!(expected, actual, "path: {}", untrusted_path);
+    }
+
+    let traversal = ["..", "..", "etc", "passwd"].join(separator.as_str());
+    let error = PathExtImpl::resolve_within("test", &traversal, false).err().unwrap();
+    assert!(matches!(error, FileExtError::OutsideRoot { .. }));
+    assert_eq!(traversal, error.path());
+}
+
+#[test]
+#[cfg(target_family = "windows")]
+fn resolve_within_drive_prefix() {
+    for untrusted_path in ["C:\\Windows", "C:Windows", "\\\\server\\share\\file"] {
+        let error = PathExtImpl::resolve_within("test", untrusted_path, false).err().unwrap();
+        assert_eq!(Some(PathRejection::AbsolutePath), error.rejection(), "path: {}", untrusted_path);
+    }
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn resolve_within_symlinks() {
+    let root = "resolve_within_root";
+    if DirectoryExtImpl::does_directory_exist(root) {
+        DirectoryExtImpl::delete_directory(root).unwrap();
+    }
+    DirectoryExtImpl::create_directory(PathExtImpl::build_path(&[root, "inner"])).unwrap();
+    std::os::unix::fs::symlink("/etc", PathExtImpl::build_path(&[root, "escape"])).unwrap();
+    std::os::unix::fs::symlink("inner", PathExtImpl::build_path(&[root, "link"])).unwrap();
+
+    let canonical_root = std::fs::canonicalize(root).unwrap().to_string_lossy().to_string();
+
+    // without symlink resolution escape is not detected
+    let path = PathExtImpl::resolve_within(root, "escape/passwd", false).unwrap();
+    assert_eq!(PathExtImpl::build_path(&[root, "escape", "passwd"]), path);
+
+    let error = PathExtImpl::resolve_within(root, "escape/passwd", true).err().unwrap();
+    assert_eq!(Some(PathRejection::SymlinkEscape), error.rejection());
+
+    let path = PathExtImpl::resolve_within(root, "link/not/created/yet.txt", true).unwrap();
+    assert_eq!(PathExtImpl::build_path(&[&canonical_root, "inner", "not", "created", "yet.txt"]), path);
+
+    let path = PathExtImpl::resolve_within(root, "", true).unwrap();
+    assert_eq!(canonical_root, path);
+
+    DirectoryExtImpl::delete_directory(root).unwrap();
+}
@@ -0,0 +1,223 @@
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(test)]
+mod tests;
+
+/// Characters and names rejected by a `PathPolicy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PathRules {
+    /// Everything `Portable` rejects, plus characters with special meaning in shell: whitespace,
+    /// quotes, backtick, `&|;$<>*?()!` and backslash within a file name. Default rules
+    #[default]
+    Strict,
+    /// Path is valid on Unix and Windows: no NUL or control characters, no `<>:"|?*`,
+    /// no reserved names like `CON` or `NUL`, no components ending with dot or space,
+    /// components are at most 255 bytes long. Whitespace is allowed
+    Portable,
+    /// Only NUL character is rejected, the operating system decides on everything else
+    Permissive,
+}
+
+/// Rules paths given to `FileExt` functions are checked against before the file system is touched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PathPolicy {
+    pub rules: PathRules,
+    /// Rejects paths with `..` components, off by default as relative paths like `../shared/config.toml`
+    /// are legitimate. Use `FileExt::resolve_within` to confine untrusted paths to a directory
+    pub reject_parent_traversal: bool,
+}
+
+/// Reason path was rejected by the policy
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PathViolation {
+    NulCharacter,
+    ControlCharacter(char),
+    /// Character which is not allowed in file names on some platform
+    ReservedCharacter(char),
+    /// Character with special meaning in shell
+    ShellCharacter(char),
+    /// Device name reserved on Windows, such as `CON`, `NUL` or `COM1`
+    ReservedName(String),
+    /// Component ending with dot or space, Windows silently strips them
+    TrailingDotOrSpace(String),
+    ComponentTooLong(String),
+    ParentTraversal,
+}
+
+impl Display for PathViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathViolation::NulCharacter => write!(f, "path contains NUL character"),
+            PathViolation::ControlCharacter(symbol) => write!(f, "path contains control character {:?}", symbol),
+            PathViolation::ReservedCharacter(symbol) => write!(f, "path contains character {:?} which is not allowed in file names", symbol),
+            PathViolation::ShellCharacter(symbol) => write!(f, "path contains character {:?} which has special meaning in shell", symbol),
+            PathViolation::ReservedName(name) => write!(f, "path contains reserved name {:?}", name),
+            PathViolation::TrailingDotOrSpace(name) => write!(f, "path contains component {:?} ending with dot or space", name),
+            PathViolation::ComponentTooLong(name) => write!(f, "path contains component longer than {} bytes: {:?}", MAX_COMPONENT_LENGTH, name),
+            PathViolation::ParentTraversal => write!(f, "path contains '..' component"),
+        }
+    }
+}
+
+const MAX_COMPONENT_LENGTH: usize = 255;
+const RESERVED_CHARACTER_LIST: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+const SHELL_CHARACTER_LIST: [char; 15] = ['\'', '"', '`', '&', '|', ';', '$', '<', '>', '*', '?', '(', ')', '!', '\\'];
+const RESERVED_NAME_LIST: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+static DEFAULT_POLICY: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static SCOPED_POLICY: Cell<Option<PathPolicy>> = const { Cell::new(None) };
+}
+
+impl PathPolicy {
+    pub const fn new(rules: PathRules) -> PathPolicy {
+        PathPolicy { rules, reject_parent_traversal: false }
+    }
+
+    /// Policy used by all `FileExt` functions, unless overridden by `with_scoped`
+    pub fn default_policy() -> PathPolicy {
+        PathPolicy::from_u8(DEFAULT_POLICY.load(Ordering::Relaxed))
+    }
+
+    /// Sets policy for the whole process
+    pub fn set_default_policy(policy: PathPolicy) {
+        DEFAULT_POLICY.store(policy.to_u8(), Ordering::Relaxed);
+    }
+
+    /// Runs the function with the policy applied to `FileExt` calls made on the current thread
+    pub fn with_scoped<T, F: FnOnce() -> T>(policy: PathPolicy, function: F) -> T {
+        let previous = SCOPED_POLICY.with(|scoped| scoped.replace(Some(policy)));
+        let _guard = ScopedPolicyGuard { previous };
+        function()
+    }
+
+    /// Scoped policy of the current thread if set, default policy otherwise
+    pub fn current() -> PathPolicy {
+        let scoped = SCOPED_POLICY.with(|scoped| scoped.get());
+        scoped.unwrap_or_else(PathPolicy::default_policy)
+    }
+
+    /// Checks the path, returns the first violation found
+    pub fn check(&self, path: &str) -> Result<(), PathViolation> {
+        if path.contains('\0') {
+            return Err(PathViolation::NulCharacter)
+        }
+
+        if self.rules != PathRules::Permissive {
+            if let Some(symbol) = path.chars().find(|symbol| symbol.is_control()) {
+                return Err(PathViolation::ControlCharacter(symbol))
+            }
+        }
+
+        // components are checked one by one, so separators and the drive prefix on Windows are never rejected
+        for component in PathPolicy::split_path(PathPolicy::strip_drive(path)) {
+            if component == ".." && self.reject_parent_traversal {
+                return Err(PathViolation::ParentTraversal)
+            }
+
+            if self.rules == PathRules::Strict {
+                if let Some(symbol) = component.chars().find(|symbol| symbol.is_whitespace() || SHELL_CHARACTER_LIST.contains(symbol)) {
+                    return Err(PathViolation::ShellCharacter(symbol))
+                }
+            }
+
+            if self.rules != PathRules::Permissive {
+                let boxed_check = PathPolicy::check_portable_component(component);
+                if boxed_check.is_err() {
+                    let violation = boxed_check.err().unwrap();
+                    return Err(violation)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_portable_component(component: &str) -> Result<(), PathViolation> {
+        if component == "." || component == ".." {
+            return Ok(())
+        }
+
+        if let Some(symbol) = component.chars().find(|symbol| RESERVED_CHARACTER_LIST.contains(symbol) || *symbol == '\\') {
+            return Err(PathViolation::ReservedCharacter(symbol))
+        }
+
+        let name = component.split('.').next().unwrap_or_default().trim_end();
+        if RESERVED_NAME_LIST.iter().any(|reserved| reserved.eq_ignore_ascii_case(name)) {
+            return Err(PathViolation::ReservedName(component.to_string()))
+        }
+
+        if component.ends_with('.') || component.ends_with(' ') {
+            return Err(PathViolation::TrailingDotOrSpace(component.to_string()))
+        }
+
+        if component.len() > MAX_COMPONENT_LENGTH {
+            return Err(PathViolation::ComponentTooLong(component.to_string()))
+        }
+
+        Ok(())
+    }
+
+    /// Drive letter is the only place colon is allowed
+    #[cfg(target_family = "windows")]
+    fn strip_drive(path: &str) -> &str {
+        let char_list : Vec<char> = path.chars().take(2).collect();
+        if char_list.len() == 2 && char_list[0].is_ascii_alphabetic() && char_list[1] == ':' {
+            return &path[2..]
+        }
+        path
+    }
+
+    #[cfg(not(target_family = "windows"))]
+    fn strip_drive(path: &str) -> &str {
+        path
+    }
+
+    #[cfg(target_family = "windows")]
+    fn split_path(path: &str) -> Vec<&str> {
+        path.split(['/', '\\']).filter(|component| !component.is_empty()).collect()
+    }
+
+    #[cfg(not(target_family = "windows"))]
+    fn split_path(path: &str) -> Vec<&str> {
+        path.split('/').filter(|component| !component.is_empty()).collect()
+    }
+
+    /// Rules in the lower bits, traversal flag in the fifth bit
+    fn to_u8(self) -> u8 {
+        let rules = match self.rules {
+            PathRules::Strict => 0,
+            PathRules::Portable => 1,
+            PathRules::Permissive => 2,
+        };
+        rules | (self.reject_parent_traversal as u8) << 4
+    }
+
+    fn from_u8(value: u8) -> PathPolicy {
+        let rules = match value & 0x0F {
+            1 => PathRules::Portable,
+            2 => PathRules::Permissive,
+            _ => PathRules::Strict,
+        };
+        PathPolicy { rules, reject_parent_traversal: value & 0x10 != 0 }
+    }
+}
+
+/// Restores previous scoped policy, even if the scoped function panics
+struct ScopedPolicyGuard {
+    previous: Option<PathPolicy>,
+}
+
+impl Drop for ScopedPolicyGuard {
+    fn drop(&mut self) {
+        SCOPED_POLICY.with(|scoped| scoped.set(self.previous));
+    }
+}
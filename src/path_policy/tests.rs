@@ -0,0 +1,119 @@
+use crate::path_policy::{PathPolicy, PathRules, PathViolation};
+
+#[test]
+fn check() {
+    let long_name = "x".repeat(256);
+    let long_path = ["folder", long_name.as_str()].join("/");
+    let case_list = [
+        ("test/index.html", Ok(()), Ok(()), Ok(())),
+        ("My Documents/report 1.pdf", Err(PathViolation::ShellCharacter(' ')), Ok(()), Ok(())),
+        ("rock&roll.mp3", Err(PathViolation::ShellCharacter('&')), Ok(()), Ok(())),
+        ("it's.txt", Err(PathViolation::ShellCharacter('\'')), Ok(()), Ok(())),
+        ("a;rm", Err(PathViolation::ShellCharacter(';')), Ok(()), Ok(())),
+        ("a|b", Err(PathViolation::ShellCharacter('|')), Err(PathViolation::ReservedCharacter('|')), Ok(())),
+        ("file\0.txt", Err(PathViolation::NulCharacter), Err(PathViolation::NulCharacter), Err(PathViolation::NulCharacter)),
+        ("file\n.txt", Err(PathViolation::ControlCharacter('\n')), Err(PathViolation::ControlCharacter('\n')), Ok(())),
+        ("../secret", Ok(()), Ok(()), Ok(())),
+        ("./folder/./file", Ok(()), Ok(()), Ok(())),
+        ("what?.txt", Err(PathViolation::ShellCharacter('?')), Err(PathViolation::ReservedCharacter('?')), Ok(())),
+        ("*.txt", Err(PathViolation::ShellCharacter('*')), Err(PathViolation::ReservedCharacter('*')), Ok(())),
+        ("a<b", Err(PathViolation::ShellCharacter('<')), Err(PathViolation::ReservedCharacter('<')), Ok(())),
+        ("a>b", Err(PathViolation::ShellCharacter('>')), Err(PathViolation::ReservedCharacter('>')), Ok(())),
+        ("$HOME", Err(PathViolation::ShellCharacter('$')), Ok(()), Ok(())),
+        ("`id`", Err(PathViolation::ShellCharacter('`')), Ok(()), Ok(())),
+        ("copy(1).txt", Err(PathViolation::ShellCharacter('(')), Ok(()), Ok(())),
+        ("copy1).txt", Err(PathViolation::ShellCharacter(')')), Ok(()), Ok(())),
+        ("hello!.txt", Err(PathViolation::ShellCharacter('!')), Ok(()), Ok(())),
+        ("file:b", Err(PathViolation::ReservedCharacter(':')), Err(PathViolation::ReservedCharacter(':')), Ok(())),
+        ("folder/CON", Err(PathViolation::ReservedName("CON".to_string())), Err(PathViolation::ReservedName("CON".to_string())), Ok(())),
+        ("nul.txt", Err(PathViolation::ReservedName("nul.txt".to_string())), Err(PathViolation::ReservedName("nul.txt".to_string())), Ok(())),
+        ("com1.log", Err(PathViolation::ReservedName("com1.log".to_string())), Err(PathViolation::ReservedName("com1.log".to_string())), Ok(())),
+        ("console.log", Ok(()), Ok(()), Ok(())),
+        ("folder./file", Err(PathViolation::TrailingDotOrSpace("folder.".to_string())), Err(PathViolation::TrailingDotOrSpace("folder.".to_string())), Ok(())),
+        ("name /file", Err(PathViolation::ShellCharacter(' ')), Err(PathViolation::TrailingDotOrSpace("name ".to_string())), Ok(())),
+        (long_path.as_str(), Err(PathViolation::ComponentTooLong(long_name.clone())), Err(PathViolation::ComponentTooLong(long_name.clone())), Ok(())),
+    ];
+
+    for (path, strict, portable, permissive) in case_list {
+        assert_eq!(strict, PathPolicy::new(PathRules::Strict).check(path), "strict: {:?}", path);
+        assert_eq!(portable, PathPolicy::new(PathRules::Portable).check(path), "portable: {:?}", path);
+        assert_eq!(permissive, PathPolicy::new(PathRules::Permissive).check(path), "permissive: {:?}", path);
+    }
+}
+
+#[test]
+fn parent_traversal() {
+    for rules in [PathRules::Strict, PathRules::Portable, PathRules::Permissive] {
+        let policy = PathPolicy { rules, reject_parent_traversal: true };
+        assert_eq!(Err(PathViolation::ParentTraversal), policy.check("../secret"), "{:?}", rules);
+        assert_eq!(Err(PathViolation::ParentTraversal), policy.check("folder/../../secret"), "{:?}", rules);
+        assert_eq!(Ok(()), policy.check("folder/..secret/file"), "{:?}", rules);
+        assert_eq!(Ok(()), policy.check("./folder/./file"), "{:?}", rules);
+    }
+
+    assert!(!PathPolicy::default().reject_parent_traversal);
+}
+
+#[test]
+#[cfg(not(target_family = "windows"))]
+fn backslash_in_file_name() {
+    assert_eq!(Err(PathViolation::ShellCharacter('\\')), PathPolicy::new(PathRules::Strict).check("a\\b"));
+    assert_eq!(Err(PathViolation::ReservedCharacter('\\')), PathPolicy::new(PathRules::Portable).check("a\\b"));
+}
+
+#[test]
+fn strict_is_superset_of_portable() {
+    let path_list = ["what?.txt", "file:b", "folder/CON", "folder./file", "name /file", "a|b", "My Documents/x", "console.log", "../up"];
+    for path in path_list {
+        if PathPolicy::new(PathRules::Portable).check(path).is_err() {
+            assert!(PathPolicy::new(PathRules::Strict).check(path).is_err(), "{:?}", path);
+        }
+    }
+}
+
+#[test]
+fn drive_letter() {
+    #[cfg(target_family = "windows")]
+    assert_eq!(Ok(()), PathPolicy::new(PathRules::Portable).check("C:\\Users\\file"));
+    #[cfg(target_family = "windows")]
+    assert_eq!(Ok(()), PathPolicy::new(PathRules::Strict).check("C:\\Users\\file"));
+    #[cfg(target_family = "unix")]
+    assert_eq!(Err(PathViolation::ReservedCharacter(':')), PathPolicy::new(PathRules::Portable).check("C:/Users/file"));
+}
+
+#[test]
+fn scoped_policy() {
+    assert_eq!(PathPolicy::default_policy(), PathPolicy::current());
+
+    let permissive = PathPolicy::new(PathRules::Permissive);
+    let portable = PathPolicy { rules: PathRules::Portable, reject_parent_traversal: true };
+    let policy = PathPolicy::with_scoped(permissive, || {
+        let nested = PathPolicy::with_scoped(portable, PathPolicy::current);
+        assert_eq!(portable, nested);
+        PathPolicy::current()
+    });
+    assert_eq!(permissive, policy);
+    assert_eq!(PathPolicy::default_policy(), PathPolicy::current());
+
+    let boxed_panic = std::panic::catch_unwind(|| {
+        PathPolicy::with_scoped(permissive, || panic!("scoped function failed"))
+    });
+    assert!(boxed_panic.is_err());
+    assert_eq!(PathPolicy::default_policy(), PathPolicy::current());
+}
+
+#[test]
+fn violation_message() {
+    assert_eq!("path contains character ' ' which has special meaning in shell", PathViolation::ShellCharacter(' ').to_string());
+    assert_eq!("path contains reserved name \"CON\"", PathViolation::ReservedName("CON".to_string()).to_string());
+}
+
+#[test]
+fn stored_policy() {
+    for rules in [PathRules::Strict, PathRules::Portable, PathRules::Permissive] {
+        for reject_parent_traversal in [false, true] {
+            let policy = PathPolicy { rules, reject_parent_traversal };
+            assert_eq!(policy, PathPolicy::from_u8(policy.to_u8()));
+        }
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod tests;
+
+/// Path syntax, independent of the operating system the code runs on.
+///
+/// Unix paths use `/` as separator and are absolute if they start with it.
+/// Windows paths accept both `\` and `/` as separators and are written back with `\`.
+/// They may start with a drive (`C:`, absolute only if followed by a separator)
+/// or with UNC prefix (`\\server\share`, always absolute). Path starting with a single separator
+/// (`\dir`) is relative to the root of the current drive, so it is not absolute.
+///
+/// Functions work only with path strings, file system is never accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    Unix,
+    Windows,
+}
+
+/// Path split into its parts: prefix (drive or UNC share), root separator and components
+struct ParsedPath<'a> {
+    prefix: String,
+    has_root: bool,
+    component_list: Vec<&'a str>,
+}
+
+impl PathStyle {
+    /// Style of the operating system the code runs on
+    #[cfg(target_family = "windows")]
+    pub fn native() -> PathStyle {
+        PathStyle::Windows
+    }
+
+    /// Style of the operating system the code runs on
+    #[cfg(not(target_family = "windows"))]
+    pub fn native() -> PathStyle {
+        PathStyle::Unix
+    }
+
+    pub fn separator(&self) -> char {
+        match self {
+            PathStyle::Unix => '/',
+            PathStyle::Windows => '\\',
+        }
+    }
+
+    fn is_separator(&self, symbol: char) -> bool {
+        match self {
+            PathStyle::Unix => symbol == '/',
+            PathStyle::Windows => symbol == '\\' || symbol == '/',
+        }
+    }
+
+    pub fn is_absolute(&self, path: &str) -> bool {
+        let parsed = self.parse(path);
+        match self {
+            PathStyle::Unix => parsed.has_root,
+            PathStyle::Windows => !parsed.prefix.is_empty() && parsed.has_root,
+        }
+    }
+
+    /// Checks if path starts with a drive or UNC prefix, always false for Unix paths
+    pub(crate) fn has_prefix(&self, path: &str) -> bool {
+        !self.parse(path).prefix.is_empty()
+    }
+
+    /// Collapses `.`, `..` and repeated separators. `..` right below the root is dropped,
+    /// leading `..` of a relative path is kept. Empty path gives `.`
+    pub fn normalize(&self, path: &str) -> String {
+        let parsed = self.parse(path);
+        let component_list = PathStyle::resolve_components(&parsed);
+        self.format(&parsed.prefix, parsed.has_root, &component_list)
+    }
+
+    /// Joins parts with a single separator between them. Separators at the end of one part
+    /// and at the beginning of the next one are collapsed, `.` and `..` are kept as is
+    pub fn join(&self, part_list: &[&str]) -> String {
+        let mut path = String::new();
+        for (index, part) in part_list.iter().enumerate() {
+            if index == 0 {
+                path.push_str(part);
+                continue;
+            }
+
+            // path consisting only of the root separator keeps it
+            let trimmed_length = path.trim_end_matches(|symbol| self.is_separator(symbol)).len();
+            if trimmed_length > 0 {
+                path.truncate(trimmed_length);
+            }
+            if !path.ends_with(|symbol| self.is_separator(symbol)) {
+                path.push(self.separator());
+            }
+            path.push_str(part.trim_start_matches(|symbol| self.is_separator(symbol)));
+        }
+        path
+    }
+
+    /// Returns path of the parent directory, normalized. Root, drive and UNC share have no parent
+    pub fn parent(&self, path: &str) -> Option<String> {
+        let parsed = self.parse(path);
+        let mut component_list = PathStyle::resolve_components(&parsed);
+
+        match component_list.last() {
+            None => {
+                if parsed.prefix.is_empty() && !parsed.has_root {
+                    return Some("..".to_string())
+                }
+                None
+            }
+            Some(&"..") => {
+                component_list.push("..");
+                Some(self.format(&parsed.prefix, parsed.has_root, &component_list))
+            }
+            Some(_) => {
+                component_list.pop();
+                Some(self.format(&parsed.prefix, parsed.has_root, &component_list))
+            }
+        }
+    }
+
+    /// Returns last component of the normalized path, None for root or path ending with `..`
+    pub fn file_name(&self, path: &str) -> Option<String> {
+        let parsed = self.parse(path);
+        let component_list = PathStyle::resolve_components(&parsed);
+        match component_list.last() {
+            None | Some(&"..") => None,
+            Some(name) => Some(name.to_string()),
+        }
+    }
+
+    /// Returns part of the file name after the last dot. Name starting with a dot (`.bashrc`) has no extension
+    pub fn extension(&self, path: &str) -> Option<String> {
+        let file_name = self.file_name(path)?;
+        let (stem, extension) = file_name.rsplit_once('.')?;
+        if stem.is_empty() {
+            return None
+        }
+        Some(extension.to_string())
+    }
+
+    /// Returns file name without extension
+    pub fn stem(&self, path: &str) -> Option<String> {
+        let file_name = self.file_name(path)?;
+        match file_name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => Some(stem.to_string()),
+            _ => Some(file_name),
+        }
+    }
+
+    /// Returns path relative to the base, both are normalized first. None if there is no such path:
+    /// one is absolute and other is not, they are on different drives, or base goes above its starting point
+    pub fn relative_to(&self, path: &str, base: &str) -> Option<String> {
+        let parsed_path = self.parse(path);
+        let parsed_base = self.parse(base);
+        if parsed_path.has_root != parsed_base.has_root || !self.is_same_component(&parsed_path.prefix, &parsed_base.prefix) {
+            return None
+        }
+
+        let path_component_list = PathStyle::resolve_components(&parsed_path);
+        let base_component_list = PathStyle::resolve_components(&parsed_base);
+
+        let common_length = path_component_list
+            .iter()
+            .zip(base_component_list.iter())
+            .take_while(|(path_component, base_component)| self.is_same_component(path_component, base_component))
+            .count();
+
+        let remaining_base = &base_component_list[common_length..];
+        if remaining_base.contains(&"..") {
+            return None
+        }
+
+        let mut component_list = vec![".."; remaining_base.len()];
+        component_list.extend_from_slice(&path_component_list[common_length..]);
+        Some(self.format("", false, &component_list))
+    }
+
+    fn is_same_component(&self, left: &str, right: &str) -> bool {
+        match self {
+            PathStyle::Unix => left == right,
+            PathStyle::Windows => left.to_lowercase() == right.to_lowercase(),
+        }
+    }
+
+    fn parse<'a>(&self, path: &'a str) -> ParsedPath<'a> {
+        let (prefix, remaining) = match self {
+            PathStyle::Unix => ("".to_string(), path),
+            PathStyle::Windows => self.split_windows_prefix(path),
+        };
+
+        let has_root = remaining.starts_with(|symbol| self.is_separator(symbol)) || prefix.starts_with("\\\\");
+        let component_list = remaining
+            .split(|symbol| self.is_separator(symbol))
+            .filter(|component| !component.is_empty())
+            .collect();
+
+        ParsedPath { prefix, has_root, component_list }
+    }
+
+    /// Splits drive (`C:`) or UNC share (`\\server\share`) from the rest of the path
+    fn split_windows_prefix<'a>(&self, path: &'a str) -> (String, &'a str) {
+        let char_list : Vec<char> = path.chars().take(2).collect();
+        if char_list.len() == 2 && self.is_separator(char_list[0]) && self.is_separator(char_list[1]) {
+            // UNC: \\server\share, verbatim \\?\C: is split the same way
+            let mut separator_count = 0;
+            let mut prefix_end = path.len();
+            for (index, symbol) in path.char_indices().skip(2) {
+                if self.is_separator(symbol) {
+                    separator_count += 1;
+                    if separator_count == 2 {
+                        prefix_end = index;
+                        break;
+                    }
+                }
+            }
+            let prefix : String = path[..prefix_end].chars().map(|symbol| if symbol == '/' { '\\' } else { symbol }).collect();
+            return (prefix, &path[prefix_end..])
+        }
+
+        if char_list.len() == 2 && char_list[0].is_ascii_alphabetic() && char_list[1] == ':' {
+            return (path[..2].to_string(), &path[2..])
+        }
+
+        ("".to_string(), path)
+    }
+
+    fn resolve_components<'a>(parsed: &ParsedPath<'a>) -> Vec<&'a str> {
+        let mut component_list : Vec<&str> = vec![];
+        for component in parsed.component_list.iter() {
+            match *component {
+                "." => {}
+                ".." => {
+                    match component_list.last() {
+                        Some(&"..") | None => {
+                            if !parsed.has_root {
+                                component_list.push("..");
+                            }
+                        }
+                        Some(_) => {
+                            component_list.pop();
+                        }
+                    }
+                }
+                _ => component_list.push(component),
+            }
+        }
+        component_list
+    }
+
+    fn format(&self, prefix: &str, has_root: bool, component_list: &[&str]) -> String {
+        let separator = self.separator().to_string();
+        let mut path = prefix.to_string();
+        if has_root {
+            path.push_str(&separator);
+        }
+        path.push_str(&component_list.join(&separator));
+
+        if path.is_empty() {
+            return ".".to_string()
+        }
+        path
+    }
+}
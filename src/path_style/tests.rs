@@ -0,0 +1,175 @@
+use crate::path_style::PathStyle;
+
+#[test]
+fn is_absolute() {
+    let case_list = [
+        (PathStyle::Unix, "/", true),
+        (PathStyle::Unix, "/home/user", true),
+        (PathStyle::Unix, "home/user", false),
+        (PathStyle::Unix, "", false),
+        (PathStyle::Unix, "C:/Users", false),
+        (PathStyle::Unix, "\\\\server\\share", false),
+        (PathStyle::Windows, "C:\\Users", true),
+        (PathStyle::Windows, "c:/Users", true),
+        (PathStyle::Windows, "C:\\", true),
+        (PathStyle::Windows, "C:", false),
+        (PathStyle::Windows, "C:Users", false),
+        (PathStyle::Windows, "\\Users", false),
+        (PathStyle::Windows, "Users\\user", false),
+        (PathStyle::Windows, "\\\\server\\share", true),
+        (PathStyle::Windows, "\\\\server\\share\\folder", true),
+        (PathStyle::Windows, "//server/share/folder", true),
+        (PathStyle::Windows, "\\\\?\\C:\\Users", true),
+    ];
+
+    for (style, path, expected) in case_list {
+        assert_eq!(expected, style.is_absolute(path), "{:?} {}", style, path);
+    }
+}
+
+#[test]
+fn normalize() {
+    let case_list = [
+        (PathStyle::Unix, "", "."),
+        (PathStyle::Unix, ".", "."),
+        (PathStyle::Unix, "./", "."),
+        (PathStyle::Unix, "/", "/"),
+        (PathStyle::Unix, "//", "/"),
+        (PathStyle::Unix, "a//b///c", "a/b/c"),
+        (PathStyle::Unix, "a/./b/.", "a/b"),
+        (PathStyle::Unix, "a/b/../c", "a/c"),
+        (PathStyle::Unix, "a/b/../../..", ".."),
+        (PathStyle::Unix, "../../a", "../../a"),
+        (PathStyle::Unix, "a/../../b", "../b"),
+        (PathStyle::Unix, "/..", "/"),
+        (PathStyle::Unix, "/a/../../b", "/b"),
+        (PathStyle::Unix, "/a/b/", "/a/b"),
+        (PathStyle::Unix, "a\\b", "a\\b"),
+        (PathStyle::Windows, "", "."),
+        (PathStyle::Windows, "a/b\\c", "a\\b\\c"),
+        (PathStyle::Windows, "C:\\a\\..\\b", "C:\\b"),
+        (PathStyle::Windows, "C:/a//b/./c/", "C:\\a\\b\\c"),
+        (PathStyle::Windows, "C:\\..\\a", "C:\\a"),
+        (PathStyle::Windows, "C:", "C:"),
+        (PathStyle::Windows, "C:a\\..\\..\\b", "C:..\\b"),
+        (PathStyle::Windows, "\\a\\..\\b", "\\b"),
+        (PathStyle::Windows, "\\\\server\\share\\a\\..\\..\\b", "\\\\server\\share\\b"),
+        (PathStyle::Windows, "//server/share/a", "\\\\server\\share\\a"),
+        (PathStyle::Windows, "\\\\server\\share", "\\\\server\\share\\"),
+        (PathStyle::Windows, "..\\a\\.\\b", "..\\a\\b"),
+    ];
+
+    for (style, path, expected) in case_list {
+        assert_eq!(expected, style.normalize(path), "{:?} {}", style, path);
+    }
+}
+
+#[test]
+fn join() {
+    let case_list : [(PathStyle, &[&str], &str); 12] = [
+        (PathStyle::Unix, &[], ""),
+        (PathStyle::Unix, &["a"], "a"),
+        (PathStyle::Unix, &["a", "b"], "a/b"),
+        (PathStyle::Unix, &["a/", "/b"], "a/b"),
+        (PathStyle::Unix, &["a///", "///b/"], "a/b/"),
+        (PathStyle::Unix, &["", "home", "user"], "/home/user"),
+        (PathStyle::Unix, &["/", "home"], "/home"),
+        (PathStyle::Unix, &["..", "..", "a"], "../../a"),
+        (PathStyle::Unix, &["a", "", "b"], "a/b"),
+        (PathStyle::Windows, &["C:", "Users", "user"], "C:\\Users\\user"),
+        (PathStyle::Windows, &["C:\\", "/Users/", "\\user"], "C:\\Users\\user"),
+        (PathStyle::Windows, &["\\\\server\\share", "folder"], "\\\\server\\share\\folder"),
+    ];
+
+    for (style, part_list, expected) in case_list {
+        assert_eq!(expected, style.join(part_list), "{:?} {:?}", style, part_list);
+    }
+}
+
+#[test]
+fn parent() {
+    let case_list = [
+        (PathStyle::Unix, "/a/b", Some("/a")),
+        (PathStyle::Unix, "/a", Some("/")),
+        (PathStyle::Unix, "/", None),
+        (PathStyle::Unix, "a/b/", Some("a")),
+        (PathStyle::Unix, "a", Some(".")),
+        (PathStyle::Unix, ".", Some("..")),
+        (PathStyle::Unix, "..", Some("../..")),
+        (PathStyle::Unix, "a/b/..", Some(".")),
+        (PathStyle::Windows, "C:\\a\\b", Some("C:\\a")),
+        (PathStyle::Windows, "C:\\a", Some("C:\\")),
+        (PathStyle::Windows, "C:\\", None),
+        (PathStyle::Windows, "C:", None),
+        (PathStyle::Windows, "\\\\server\\share\\a", Some("\\\\server\\share\\")),
+        (PathStyle::Windows, "\\\\server\\share", None),
+    ];
+
+    for (style, path, expected) in case_list {
+        assert_eq!(expected.map(|parent| parent.to_string()), style.parent(path), "{:?} {}", style, path);
+    }
+}
+
+#[test]
+fn file_name_extension_stem() {
+    let case_list = [
+        (PathStyle::Unix, "/a/file.txt", Some("file.txt"), Some("txt"), Some("file")),
+        (PathStyle::Unix, "archive.tar.gz", Some("archive.tar.gz"), Some("gz"), Some("archive.tar")),
+        (PathStyle::Unix, "a/.bashrc", Some(".bashrc"), None, Some(".bashrc")),
+        (PathStyle::Unix, "a/.config.toml", Some(".config.toml"), Some("toml"), Some(".config")),
+        (PathStyle::Unix, "a/Makefile", Some("Makefile"), None, Some("Makefile")),
+        (PathStyle::Unix, "a/file.", Some("file."), Some(""), Some("file")),
+        (PathStyle::Unix, "a/b/", Some("b"), None, Some("b")),
+        (PathStyle::Unix, "a/b/.", Some("b"), None, Some("b")),
+        (PathStyle::Unix, "a/..", None, None, None),
+        (PathStyle::Unix, "/", None, None, None),
+        (PathStyle::Unix, "", None, None, None),
+        (PathStyle::Unix, "a\\b.txt", Some("a\\b.txt"), Some("txt"), Some("a\\b")),
+        (PathStyle::Windows, "C:\\a\\file.txt", Some("file.txt"), Some("txt"), Some("file")),
+        (PathStyle::Windows, "C:/a/b.c/file", Some("file"), None, Some("file")),
+        (PathStyle::Windows, "C:\\", None, None, None),
+        (PathStyle::Windows, "\\\\server\\share", None, None, None),
+    ];
+
+    for (style, path, file_name, extension, stem) in case_list {
+        assert_eq!(file_name.map(|value| value.to_string()), style.file_name(path), "file name {:?} {}", style, path);
+        assert_eq!(extension.map(|value| value.to_string()), style.extension(path), "extension {:?} {}", style, path);
+        assert_eq!(stem.map(|value| value.to_string()), style.stem(path), "stem {:?} {}", style, path);
+    }
+}
+
+#[test]
+fn relative_to() {
+    let case_list = [
+        (PathStyle::Unix, "/a/b/c", "/a", Some("b/c")),
+        (PathStyle::Unix, "/a/b/c", "/a/d", Some("../b/c")),
+        (PathStyle::Unix, "/a", "/a/b/c", Some("../..")),
+        (PathStyle::Unix, "/a", "/a", Some(".")),
+        (PathStyle::Unix, "/a/./b/../c", "/a//", Some("c")),
+        (PathStyle::Unix, "a/b", "c", Some("../a/b")),
+        (PathStyle::Unix, "../a", "..", Some("a")),
+        (PathStyle::Unix, "a", "../b", None),
+        (PathStyle::Unix, "/a", "a", None),
+        (PathStyle::Unix, "/A", "/a", Some("../A")),
+        (PathStyle::Windows, "C:\\a\\b", "c:\\A", Some("b")),
+        (PathStyle::Windows, "C:\\a\\b", "D:\\a", None),
+        (PathStyle::Windows, "\\\\server\\share\\a\\b", "\\\\SERVER\\share\\c", Some("..\\a\\b")),
+        (PathStyle::Windows, "\\\\server\\share\\a", "\\\\other\\share\\a", None),
+        (PathStyle::Windows, "a\\b", "a/c", Some("..\\b")),
+    ];
+
+    for (style, path, base, expected) in case_list {
+        assert_eq!(expected.map(|value| value.to_string()), style.relative_to(path, base), "{:?} {} {}", style, path, base);
+    }
+}
+
+#[test]
+fn separator() {
+    assert_eq!('/', PathStyle::Unix.separator());
+    assert_eq!('\\', PathStyle::Windows.separator());
+
+    #[cfg(target_family = "unix")]
+    assert_eq!(PathStyle::Unix, PathStyle::native());
+    #[cfg(target_family = "windows")]
+    assert_eq!(PathStyle::Windows, PathStyle::native());
+}
@@ -0,0 +1,415 @@
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use crate::date_time_ext::DateTimeExt;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::file_ext_impl::FileExtImpl;
+use crate::FileExt;
+use crate::filter_string::FilterString;
+use crate::symbol::SYMBOL;
+
+#[cfg(test)]
+mod tests;
+
+/// Result of the resumable copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumableCopyStatus {
+    /// Whole file is copied, checkpoint is removed. `resumed_from` is the byte copy continued from, 0 for a fresh copy.
+    Completed { bytes_copied: u64, resumed_from: u64 },
+    /// Copy was cancelled by the cancel callback, checkpoint is kept so next invocation continues from `bytes_copied`
+    Cancelled { bytes_copied: u64 },
+}
+
+/// State of the copy persisted next to the destination file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CopyCheckpoint {
+    pub source: String,
+    pub size: u64,
+    pub modified: u128,
+    pub bytes_done: u64,
+    pub hash: u64,
+}
+
+impl CopyCheckpoint {
+    pub const FILE_EXTENSION: &'static str = "checkpoint";
+
+    pub fn path<P: AsRef<Path>>(destination: P) -> PathBuf {
+        let mut path = destination.as_ref().as_os_str().to_os_string();
+        path.push(".");
+        path.push(CopyCheckpoint::FILE_EXTENSION);
+        PathBuf::from(path)
+    }
+
+    pub fn generate(&self) -> String {
+        let line_list = [
+            format!("source{}{}", SYMBOL.equals, CopyCheckpoint::escape(&self.source)),
+            format!("size{}{}", SYMBOL.equals, self.size),
+            format!("modified{}{}", SYMBOL.equals, self.modified),
+            format!("bytes_done{}{}", SYMBOL.equals, self.bytes_done),
+            format!("hash{}{:016x}", SYMBOL.equals, self.hash),
+        ];
+        line_list.join(SYMBOL.new_line)
+    }
+
+    pub fn parse(text: &str) -> Option<CopyCheckpoint> {
+        let mut source = None;
+        let mut size = None;
+        let mut modified = None;
+        let mut bytes_done = None;
+        let mut hash = None;
+
+        for line in text.lines() {
+            let (key, value) = line.split_once(SYMBOL.equals)?;
+            match key {
+                "source" => source = CopyCheckpoint::unescape(value),
+                "size" => size = value.parse::<u64>().ok(),
+                "modified" => modified = value.parse::<u128>().ok(),
+                "bytes_done" => bytes_done = value.parse::<u64>().ok(),
+                "hash" => hash = u64::from_str_radix(value, 16).ok(),
+                _ => {}
+            }
+        }
+
+        Some(CopyCheckpoint {
+            source: source?,
+            size: size?,
+            modified: modified?,
+            bytes_done: bytes_done?,
+            hash: hash?,
+        })
+    }
+
+    /// Keeps the value on a single line, file names on Unix may contain line breaks
+    fn escape(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+    }
+
+    fn unescape(value: &str) -> Option<String> {
+        let mut unescaped = String::with_capacity(value.len());
+        let mut char_iterator = value.chars();
+        while let Some(symbol) = char_iterator.next() {
+            if symbol != '\\' {
+                unescaped.push(symbol);
+                continue;
+            }
+
+            match char_iterator.next()? {
+                '\\' => unescaped.push('\\'),
+                'n' => unescaped.push('\n'),
+                'r' => unescaped.push('\r'),
+                _ => return None,
+            }
+        }
+        Some(unescaped)
+    }
+}
+
+/// 64-bit FNV-1a, can be updated block by block
+pub(crate) struct RollingHash {
+    value: u64,
+}
+
+impl RollingHash {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    pub fn new() -> RollingHash {
+        RollingHash { value: RollingHash::OFFSET_BASIS }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.value ^= *byte as u64;
+            self.value = self.value.wrapping_mul(RollingHash::PRIME);
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+pub struct ResumableCopyImpl;
+
+impl ResumableCopyImpl {
+    /// Checkpoint is saved after this many bytes were copied since the previous one
+    pub const CHECKPOINT_INTERVAL_BYTES: u64 = 8 * 1024 * 1024;
+    /// Checkpoint is saved if this much time passed since the previous one, slow copies still make progress
+    pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(2);
+
+    pub fn copy_file_resumable
+    <F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: Vec<&str>,
+        to: Vec<&str>,
+        block_size: Option<u64>,
+        overwrite: bool,
+        progress_callback: F,
+        cancel_callback: C,
+    )
+        -> Result<ResumableCopyStatus, FileExtError> {
+        let from_path = FileExt::build_path(&from);
+        let to_path = FileExt::build_path(&to);
+        ResumableCopyImpl::copy_path_resumable(from_path, to_path, block_size, overwrite, progress_callback, cancel_callback)
+    }
+
+    /// Same as `copy_file_resumable`, source and destination are given as OS paths
+    pub fn copy_path_resumable
+    <P: AsRef<Path>, Q: AsRef<Path>, F: FnMut(u64, u64, u64), C: FnMut(u64, u64, u64) -> bool>
+    (
+        from: P,
+        to: Q,
+        block_size: Option<u64>,
+        overwrite: bool,
+        mut progress_callback: F,
+        mut cancel_callback: C,
+    )
+        -> Result<ResumableCopyStatus, FileExtError> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+        let from_path = from.to_string_lossy().to_string();
+        let to_path = to.to_string_lossy().to_string();
+        let checkpoint_path = CopyCheckpoint::path(to);
+
+        let _100kb = 102400;
+        let step = block_size.unwrap_or(_100kb);
+        if step == 0 {
+            let message = "block size must be greater than 0".to_string();
+            return Err(FileExtError::from_kind(Operation::Copy, from_path.as_str(), ErrorKind::InvalidInput, message));
+        }
+
+        for path in [from, to] {
+            let boxed_check = FilterString::is_valid_input_path(path);
+            if boxed_check.is_err() {
+                let message = boxed_check.err().unwrap();
+                return Err(FileExtError::invalid_path(Operation::Copy, &path.to_string_lossy(), message))
+            }
+        }
+
+        // destination without checkpoint was not created by an interrupted copy, it is not ours to truncate
+        let is_destination_occupied = FileExtImpl::does_file_exist(to) && !FileExtImpl::does_file_exist(&checkpoint_path);
+        if is_destination_occupied && !overwrite {
+            let message = format!("destination already exists and there is no checkpoint to resume from: {}", to_path);
+            return Err(FileExtError::from_kind(Operation::Copy, to_path.as_str(), ErrorKind::AlreadyExists, message))
+        }
+
+        let boxed_source = File::open(from);
+        if boxed_source.is_err() {
+            let error = boxed_source.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, from_path.as_str(), error))
+        }
+        let mut source = boxed_source.unwrap();
+
+        let boxed_metadata = source.metadata();
+        if boxed_metadata.is_err() {
+            let error = boxed_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, from_path.as_str(), error))
+        }
+        let metadata = boxed_metadata.unwrap();
+        let size = metadata.len();
+        let mut modified = 0;
+        if let Ok(modified_time) = metadata.modified() {
+            modified = DateTimeExt::_system_time_to_unix_nanos(modified_time);
+        }
+
+        let mut checkpoint = CopyCheckpoint {
+            source: from_path.to_string(),
+            size,
+            modified,
+            bytes_done: 0,
+            hash: RollingHash::new().value(),
+        };
+        let mut hash = RollingHash::new();
+
+        // checkpoint is created before the destination, so destination left by a crash is recognized on the next call
+        if !FileExtImpl::does_file_exist(&checkpoint_path) {
+            let boxed_checkpoint = FileExtImpl::write_file_atomically(&checkpoint_path, checkpoint.generate().as_bytes());
+            if boxed_checkpoint.is_err() {
+                let error = boxed_checkpoint.err().unwrap();
+                return Err(error)
+            }
+        }
+
+        let boxed_destination = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(to);
+        if boxed_destination.is_err() {
+            let error = boxed_destination.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Open, to_path.as_str(), error))
+        }
+        let mut destination = boxed_destination.unwrap();
+
+        let boxed_previous_checkpoint = ResumableCopyImpl::read_checkpoint(&checkpoint_path);
+        if let Some(previous_checkpoint) = &boxed_previous_checkpoint {
+            let same_source =
+                previous_checkpoint.source == checkpoint.source &&
+                previous_checkpoint.size == checkpoint.size &&
+                previous_checkpoint.modified == checkpoint.modified &&
+                previous_checkpoint.bytes_done <= size;
+
+            if same_source {
+                let boxed_prefix_hash = ResumableCopyImpl::hash_prefix(&mut destination, to_path.as_str(), previous_checkpoint.bytes_done);
+                if boxed_prefix_hash.is_err() {
+                    let error = boxed_prefix_hash.err().unwrap();
+                    return Err(error)
+                }
+
+                if let Some(prefix_hash) = boxed_prefix_hash.unwrap() {
+                    if prefix_hash.value() == previous_checkpoint.hash {
+                        checkpoint.bytes_done = previous_checkpoint.bytes_done;
+                        checkpoint.hash = previous_checkpoint.hash;
+                        hash = prefix_hash;
+                    }
+                }
+            }
+        }
+        let resumed_from = checkpoint.bytes_done;
+
+        // checkpoint of a different source or an unverified destination is replaced before the destination is truncated,
+        // so a crash during the restart never resumes from it
+        let is_checkpoint_stale = boxed_previous_checkpoint.as_ref() != Some(&checkpoint);
+        if resumed_from == 0 && is_checkpoint_stale {
+            let boxed_checkpoint = FileExtImpl::write_file_atomically(&checkpoint_path, checkpoint.generate().as_bytes());
+            if boxed_checkpoint.is_err() {
+                let error = boxed_checkpoint.err().unwrap();
+                return Err(error)
+            }
+        }
+
+        // anything after the verified prefix was written after the last checkpoint and is copied again
+        let boxed_truncate = destination.set_len(resumed_from);
+        if boxed_truncate.is_err() {
+            let error = boxed_truncate.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Write, to_path.as_str(), error))
+        }
+
+        for (file, path) in [(&mut source, from_path.as_str()), (&mut destination, to_path.as_str())] {
+            let boxed_seek = file.seek(SeekFrom::Start(resumed_from));
+            if boxed_seek.is_err() {
+                let error = boxed_seek.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Seek, path, error))
+            }
+        }
+
+        let mut buffer = vec![0; step.min(size.max(1)) as usize];
+        let mut checkpoint_bytes_done = checkpoint.bytes_done;
+        let mut checkpoint_time = Instant::now();
+        while checkpoint.bytes_done < size {
+            let block_length = step.min(size - checkpoint.bytes_done);
+            let block_start = checkpoint.bytes_done;
+            let block_end = block_start + block_length - 1;
+            progress_callback(block_start, block_end, size);
+
+            let block = &mut buffer[..block_length as usize];
+            let boxed_read = source.read_exact(block);
+            if boxed_read.is_err() {
+                let error = boxed_read.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Read, from_path.as_str(), error))
+            }
+
+            let boxed_write = destination.write_all(block);
+            if boxed_write.is_err() {
+                let error = boxed_write.err().unwrap();
+                return Err(FileExtError::from_io(Operation::Write, to_path.as_str(), error))
+            }
+
+            hash.update(block);
+            checkpoint.bytes_done += block_length;
+            checkpoint.hash = hash.value();
+
+            let copying_cancelled_by_user = cancel_callback(block_start, block_end, size) && checkpoint.bytes_done < size;
+            let is_checkpoint_due = ResumableCopyImpl::is_checkpoint_due(checkpoint.bytes_done - checkpoint_bytes_done, checkpoint_time.elapsed());
+            if copying_cancelled_by_user || (is_checkpoint_due && checkpoint.bytes_done < size) {
+                let boxed_save = ResumableCopyImpl::save_checkpoint(&destination, to_path.as_str(), &checkpoint_path, &checkpoint);
+                if boxed_save.is_err() {
+                    let error = boxed_save.err().unwrap();
+                    return Err(error)
+                }
+                checkpoint_bytes_done = checkpoint.bytes_done;
+                checkpoint_time = Instant::now();
+            }
+
+            if copying_cancelled_by_user {
+                return Ok(ResumableCopyStatus::Cancelled { bytes_copied: checkpoint.bytes_done })
+            }
+        }
+
+        if FileExtImpl::does_file_exist(&checkpoint_path) {
+            let boxed_delete = FileExtImpl::delete_file(&checkpoint_path);
+            if boxed_delete.is_err() {
+                let error = boxed_delete.err().unwrap();
+                return Err(error)
+            }
+        }
+
+        Ok(ResumableCopyStatus::Completed { bytes_copied: size, resumed_from })
+    }
+
+    pub(crate) fn is_checkpoint_due(bytes_since_checkpoint: u64, time_since_checkpoint: Duration) -> bool {
+        bytes_since_checkpoint >= ResumableCopyImpl::CHECKPOINT_INTERVAL_BYTES
+            || time_since_checkpoint >= ResumableCopyImpl::CHECKPOINT_INTERVAL
+    }
+
+    fn save_checkpoint(destination: &File, to_path: &str, checkpoint_path: &Path, checkpoint: &CopyCheckpoint) -> Result<(), FileExtError> {
+        // checkpoint must never point past the data that reached the disk
+        let boxed_sync = destination.sync_data();
+        if boxed_sync.is_err() {
+            let error = boxed_sync.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Write, to_path, error))
+        }
+
+        FileExtImpl::write_file_atomically(checkpoint_path, checkpoint.generate().as_bytes())
+    }
+
+    fn read_checkpoint(checkpoint_path: &Path) -> Option<CopyCheckpoint> {
+        if !FileExtImpl::does_file_exist(checkpoint_path) {
+            return None
+        }
+
+        let boxed_content = FileExtImpl::read_file(checkpoint_path);
+        if boxed_content.is_err() {
+            return None
+        }
+
+        let content = boxed_content.unwrap();
+        let text = String::from_utf8_lossy(&content);
+        CopyCheckpoint::parse(&text)
+    }
+
+    /// Hashes first `length` bytes of the destination, returns None if destination is shorter
+    fn hash_prefix(destination: &mut File, path: &str, length: u64) -> Result<Option<RollingHash>, FileExtError> {
+        let boxed_seek = destination.seek(SeekFrom::Start(0));
+        if boxed_seek.is_err() {
+            let error = boxed_seek.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Seek, path, error))
+        }
+
+        let mut hash = RollingHash::new();
+        let mut buffer = vec![0; 8192];
+        let mut remaining = length;
+        while remaining > 0 {
+            let chunk_length = remaining.min(buffer.len() as u64) as usize;
+            let boxed_read = destination.read(&mut buffer[..chunk_length]);
+            if boxed_read.is_err() {
+                let error = boxed_read.err().unwrap();
+                if error.kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(FileExtError::from_io(Operation::Read, path, error))
+            }
+
+            let bytes_read = boxed_read.unwrap();
+            if bytes_read == 0 {
+                return Ok(None)
+            }
+            hash.update(&buffer[..bytes_read]);
+            remaining -= bytes_read as u64;
+        }
+
+        Ok(Some(hash))
+    }
+}
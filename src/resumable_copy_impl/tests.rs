@@ -0,0 +1,251 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::time::Duration;
+use crate::file_ext_impl::FileExtImpl;
+use crate::resumable_copy_impl::{CopyCheckpoint, ResumableCopyImpl, ResumableCopyStatus, RollingHash};
+use crate::write_mode::WriteMode;
+
+fn create_source(path: &str, length: u32) -> Vec<u8> {
+    let content : Vec<u8> = (0..length).map(|index| (index % 251) as u8).collect();
+    FileExtImpl::write_file_with_mode(path, &content, WriteMode::CreateOrTruncate).unwrap();
+    content
+}
+
+fn cleanup(path_list: &[&str]) {
+    for path in path_list {
+        if FileExtImpl::does_file_exist(path) {
+            FileExtImpl::delete_file(path).unwrap();
+        }
+    }
+}
+
+#[test]
+fn checkpoint_generate_parse() {
+    let checkpoint = CopyCheckpoint {
+        source: "/home/someuser/file.bin".to_string(),
+        size: 1000,
+        modified: 1672531200000000000,
+        bytes_done: 300,
+        hash: 0xcbf29ce484222325,
+    };
+
+    let text = checkpoint.generate();
+    assert_eq!(Some(checkpoint), CopyCheckpoint::parse(&text));
+
+    let checkpoint = CopyCheckpoint {
+        source: "/home/someuser/line\nbreak\\n\r.bin".to_string(),
+        size: 1000,
+        modified: 1672531200000000000,
+        bytes_done: 300,
+        hash: 0xcbf29ce484222325,
+    };
+    let text = checkpoint.generate();
+    assert_eq!(5, text.lines().count());
+    assert_eq!(Some(checkpoint), CopyCheckpoint::parse(&text));
+
+    assert_eq!(None, CopyCheckpoint::parse("source=/file\nsize=10"));
+    assert_eq!(None, CopyCheckpoint::parse("source=/file\\x\nsize=1\nmodified=1\nbytes_done=1\nhash=1"));
+    assert_eq!(None, CopyCheckpoint::parse("garbage"));
+    assert_eq!(Path::new("out.bin.checkpoint"), CopyCheckpoint::path("out.bin"));
+}
+
+#[test]
+fn rolling_hash_is_incremental() {
+    let mut whole = RollingHash::new();
+    whole.update(b"hello world");
+
+    let mut parts = RollingHash::new();
+    parts.update(b"hello");
+    parts.update(b" world");
+
+    assert_eq!(whole.value(), parts.value());
+    assert_ne!(RollingHash::new().value(), whole.value());
+}
+
+#[test]
+fn copy_without_interruption() {
+    let from = "test/resumable_copy-full-source.bin";
+    let to = "test/resumable_copy-full-destination.bin";
+    let checkpoint_path = CopyCheckpoint::path(to);
+    let content = create_source(from, 1000);
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(128), false, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 1000, resumed_from: 0 }, status);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+    assert!(!FileExtImpl::does_file_exist(&checkpoint_path));
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn resume_after_cancel() {
+    let from = "test/resumable_copy-cancel-source.bin";
+    let to = "test/resumable_copy-cancel-destination.bin";
+    let checkpoint_path = CopyCheckpoint::path(to);
+    let content = create_source(from, 1000);
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, end, _| end >= 299).unwrap();
+    assert_eq!(ResumableCopyStatus::Cancelled { bytes_copied: 300 }, status);
+    assert!(FileExtImpl::does_file_exist(&checkpoint_path));
+    assert_eq!(&content[..300], FileExtImpl::read_file(to).unwrap().as_slice());
+
+    let mut block_list = vec![];
+    let status = ResumableCopyImpl::copy_file_resumable(
+        vec![from],
+        vec![to],
+        Some(100),
+        false,
+        |start, end, _| block_list.push((start, end)),
+        |_, _, _| false,
+    ).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 1000, resumed_from: 300 }, status);
+    assert_eq!((300, 399), block_list[0]);
+    assert_eq!(7, block_list.len());
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+    assert!(!FileExtImpl::does_file_exist(&checkpoint_path));
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn resume_after_crash_discards_unrecorded_bytes() {
+    let from = "test/resumable_copy-crash-source.bin";
+    let to = "test/resumable_copy-crash-destination.bin";
+    let content = create_source(from, 1000);
+
+    ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| true).unwrap();
+
+    // simulate crash after a block was written but before the checkpoint was updated
+    let mut destination = WriteMode::Append.open_options().open(to).unwrap();
+    destination.write_all(b"partially written block").unwrap();
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 1000, resumed_from: 100 }, status);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn restart_when_destination_prefix_does_not_match() {
+    let from = "test/resumable_copy-tampered-source.bin";
+    let to = "test/resumable_copy-tampered-destination.bin";
+    let content = create_source(from, 1000);
+
+    ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, end, _| end >= 199).unwrap();
+
+    let mut tampered = FileExtImpl::read_file(to).unwrap();
+    tampered[10] = tampered[10].wrapping_add(1);
+    FileExtImpl::write_file_with_mode(to, &tampered, WriteMode::Overwrite).unwrap();
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 1000, resumed_from: 0 }, status);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn restart_when_source_changed() {
+    let from = "test/resumable_copy-changed-source.bin";
+    let to = "test/resumable_copy-changed-destination.bin";
+    create_source(from, 1000);
+
+    ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| true).unwrap();
+
+    let changed_content = create_source(from, 500);
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 500, resumed_from: 0 }, status);
+    assert_eq!(changed_content, FileExtImpl::read_file(to).unwrap());
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn restart_when_source_rewritten_with_same_length() {
+    let from = "test/resumable_copy-rewritten-source.bin";
+    let to = "test/resumable_copy-rewritten-destination.bin";
+    let mut content = create_source(from, 1000);
+
+    ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| true).unwrap();
+    let checkpoint_path = CopyCheckpoint::path(to);
+    let stale_checkpoint = CopyCheckpoint::parse(&String::from_utf8(FileExtImpl::read_file(&checkpoint_path).unwrap()).unwrap()).unwrap();
+
+    // copied prefix is unchanged, so only the modification time tells the source apart
+    content[500] = content[500].wrapping_add(1);
+    FileExtImpl::write_file_with_mode(from, &content, WriteMode::Overwrite).unwrap();
+    let modified = std::time::UNIX_EPOCH + Duration::from_nanos((stale_checkpoint.modified as u64) + 1_000_000_000);
+    fs::File::options().write(true).open(from).unwrap().set_modified(modified).unwrap();
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| true).unwrap();
+    assert_eq!(ResumableCopyStatus::Cancelled { bytes_copied: 100 }, status);
+    let checkpoint = CopyCheckpoint::parse(&String::from_utf8(FileExtImpl::read_file(&checkpoint_path).unwrap()).unwrap()).unwrap();
+    assert_ne!(stale_checkpoint.modified, checkpoint.modified);
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 1000, resumed_from: 100 }, status);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn copy_empty_file() {
+    let from = "test/resumable_copy-empty-source.bin";
+    let to = "test/resumable_copy-empty-destination.bin";
+    create_source(from, 0);
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], None, false, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 0, resumed_from: 0 }, status);
+    assert_eq!(0, fs::metadata(to).unwrap().len());
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn existing_destination_is_not_overwritten() {
+    let from = "test/resumable_copy-existing-source.bin";
+    let to = "test/resumable_copy-existing-destination.bin";
+    let content = create_source(from, 1000);
+    FileExtImpl::write_file_with_mode(to, b"keep me", WriteMode::CreateOrTruncate).unwrap();
+
+    let error = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {}, |_, _, _| false).err().unwrap();
+    assert_eq!(ErrorKind::AlreadyExists, error.kind());
+    assert_eq!(b"keep me".to_vec(), FileExtImpl::read_file(to).unwrap());
+    assert!(!FileExtImpl::does_file_exist(CopyCheckpoint::path(to)));
+
+    let status = ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), true, |_, _, _| {}, |_, _, _| false).unwrap();
+    assert_eq!(ResumableCopyStatus::Completed { bytes_copied: 1000, resumed_from: 0 }, status);
+    assert_eq!(content, FileExtImpl::read_file(to).unwrap());
+
+    cleanup(&[from, to]);
+}
+
+#[test]
+fn checkpoint_is_throttled() {
+    let interval = ResumableCopyImpl::CHECKPOINT_INTERVAL_BYTES;
+    assert!(!ResumableCopyImpl::is_checkpoint_due(100, Duration::from_millis(10)));
+    assert!(!ResumableCopyImpl::is_checkpoint_due(interval - 1, Duration::ZERO));
+    assert!(ResumableCopyImpl::is_checkpoint_due(interval, Duration::ZERO));
+    assert!(ResumableCopyImpl::is_checkpoint_due(1, ResumableCopyImpl::CHECKPOINT_INTERVAL));
+}
+
+#[test]
+fn checkpoint_is_not_saved_for_every_block() {
+    let from = "test/resumable_copy-throttled-source.bin";
+    let to = "test/resumable_copy-throttled-destination.bin";
+    let checkpoint_path = CopyCheckpoint::path(to);
+    create_source(from, 1000);
+
+    let mut checkpoint_list = vec![];
+    ResumableCopyImpl::copy_file_resumable(vec![from], vec![to], Some(100), false, |_, _, _| {
+        checkpoint_list.push(CopyCheckpoint::parse(&String::from_utf8_lossy(&FileExtImpl::read_file(&checkpoint_path).unwrap())).unwrap().bytes_done);
+    }, |_, _, _| false).unwrap();
+
+    // only the initial checkpoint is written, 1000 bytes is way below the interval
+    assert_eq!(vec![0; 10], checkpoint_list);
+    assert!(!FileExtImpl::does_file_exist(&checkpoint_path));
+
+    cleanup(&[from, to]);
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+#[allow(dead_code)]
 pub struct Symbol {
     pub new_line_carriage_return: &'static str,
     pub new_line: &'static str,
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+use std::fs;
+use std::fs::Metadata;
+use std::io;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use crate::file_ext_error::{FileExtError, Operation};
+
+#[cfg(test)]
+mod tests;
+
+/// Result of following a symlink through all of its hops
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymlinkChain {
+    /// Given path followed by the target of every hop, relative targets are joined with the directory of the link
+    pub hop_list: Vec<String>,
+    /// Canonical absolute path of the final target, intermediate directories are resolved as well
+    pub target: String,
+    /// Same as `hop_list`, but file names which are not valid UTF-8 are kept as is instead of replaced by U+FFFD
+    pub os_hop_list: Vec<PathBuf>,
+    /// Same as `target`, without lossy conversion
+    pub os_target: PathBuf,
+}
+
+impl SymlinkChain {
+    /// Maximum number of symlinks followed, the same limit Linux uses before failing with `ELOOP`
+    pub const MAX_DEPTH: usize = 40;
+
+    pub(crate) fn resolve<P: AsRef<Path>>(path: P, max_depth: usize) -> Result<SymlinkChain, FileExtError> {
+        let path = path.as_ref();
+        let filepath = path.to_string_lossy().to_string();
+        let mut current = path.to_path_buf();
+        let mut os_hop_list = vec![current.clone()];
+        let mut visited = HashSet::new();
+
+        loop {
+            let current_path = current.to_string_lossy().to_string();
+            let boxed_metadata = fs::symlink_metadata(&current);
+            if boxed_metadata.is_err() {
+                let error = boxed_metadata.err().unwrap();
+                return Err(FileExtError::from_io(Operation::ResolveSymlink, &current_path, error))
+            }
+
+            let metadata = boxed_metadata.unwrap();
+            if !metadata.file_type().is_symlink() {
+                break;
+            }
+
+            let boxed_key = SymlinkChain::visited_key(&metadata, &current);
+            if boxed_key.is_err() {
+                let error = boxed_key.err().unwrap();
+                return Err(FileExtError::from_io(Operation::ResolveSymlink, &current_path, error))
+            }
+
+            let is_new = visited.insert(boxed_key.unwrap());
+            if !is_new {
+                let message = format!("symlink loop detected at {}", current_path);
+                return Err(FileExtError::from_kind(Operation::ResolveSymlink, &filepath, ErrorKind::InvalidData, message))
+            }
+
+            if os_hop_list.len() > max_depth {
+                let message = format!("too many levels of symbolic links, limit is {}", max_depth);
+                return Err(FileExtError::from_kind(Operation::ResolveSymlink, &filepath, ErrorKind::InvalidData, message))
+            }
+
+            let boxed_points_to = fs::read_link(&current);
+            if boxed_points_to.is_err() {
+                let error = boxed_points_to.err().unwrap();
+                return Err(FileExtError::from_io(Operation::ReadSymlink, &current_path, error))
+            }
+
+            // joining an absolute target replaces the base
+            let symlink_directory = current.parent().unwrap_or(Path::new("")).to_path_buf();
+            current = symlink_directory.join(boxed_points_to.unwrap());
+            os_hop_list.push(current.clone());
+        }
+
+        let boxed_target = fs::canonicalize(&current);
+        if boxed_target.is_err() {
+            let error = boxed_target.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ResolveSymlink, &current.to_string_lossy(), error))
+        }
+
+        let os_target = boxed_target.unwrap();
+        let hop_list = os_hop_list.iter().map(|hop| hop.to_string_lossy().to_string()).collect();
+        let target = os_target.to_string_lossy().to_string();
+        Ok(SymlinkChain { hop_list, target, os_hop_list, os_target })
+    }
+
+    /// Device and inode of the link itself, the same link reached via different spellings
+    /// or through symlinked directories gives the same key
+    #[cfg(target_family = "unix")]
+    fn visited_key(metadata: &Metadata, _path: &Path) -> io::Result<String> {
+        use std::os::unix::fs::MetadataExt;
+
+        Ok(format!("{}:{}", metadata.dev(), metadata.ino()))
+    }
+
+    /// Link name within its canonical directory, symlinked directories on the way are resolved
+    #[cfg(not(target_family = "unix"))]
+    fn visited_key(_metadata: &Metadata, path: &Path) -> io::Result<String> {
+        let directory = path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let boxed_directory = fs::canonicalize(directory);
+        if boxed_directory.is_err() {
+            let error = boxed_directory.err().unwrap();
+            return Err(error)
+        }
+
+        let key = boxed_directory.unwrap().join(path.file_name().unwrap_or_default());
+        Ok(key.to_string_lossy().to_string())
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::io::ErrorKind;
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::FileExtError;
+use crate::file_ext_impl::FileExtImpl;
+use crate::path_ext_impl::PathExtImpl;
+use crate::symlink_chain::SymlinkChain;
+
+#[cfg(target_family = "unix")]
+fn link(directory: &str, name: &str, points_to: &str) -> String {
+    let path = PathExtImpl::build_path(&[directory, name]);
+    std::os::unix::fs::symlink(points_to, &path).unwrap();
+    path
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn multiple_hops() {
+    let directory = "symlink_chain_multiple_hops";
+    let nested = PathExtImpl::build_path(&[directory, "nested"]);
+    DirectoryExtImpl::create_directory(&nested).unwrap();
+    let file = PathExtImpl::build_path(&[nested.as_str(), "file.txt"]);
+    FileExtImpl::create_file(&file).unwrap();
+
+    let first = link(directory, "first", "nested/second");
+    link(&nested, "second", "third");
+    link(&nested, "third", "file.txt");
+
+    let chain = SymlinkChain::resolve(&first, SymlinkChain::MAX_DEPTH).unwrap();
+    let expected_hop_list = vec![
+        first.clone(),
+        PathExtImpl::build_path(&[directory, "nested", "second"]),
+        PathExtImpl::build_path(&[directory, "nested", "third"]),
+        file.clone(),
+    ];
+    assert_eq!(expected_hop_list, chain.hop_list);
+    assert_eq!(fs::canonicalize(&file).unwrap().to_string_lossy(), chain.target);
+
+    let error = SymlinkChain::resolve(&first, 2).err().unwrap();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert!(SymlinkChain::resolve(&first, 3).is_ok());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn loop_is_detected() {
+    let directory = "symlink_chain_loop_is_detected";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+
+    let first = link(directory, "first", "second");
+    link(directory, "second", "./first");
+    let own = link(directory, "own", "own");
+
+    let error = SymlinkChain::resolve(&first, SymlinkChain::MAX_DEPTH).err().unwrap();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert!(error.message().contains("loop"));
+
+    let error = SymlinkChain::resolve(&own, SymlinkChain::MAX_DEPTH).err().unwrap();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn broken_chain() {
+    let directory = "symlink_chain_broken_chain";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+
+    let first = link(directory, "first", "second");
+    link(directory, "second", "missing");
+
+    let error = SymlinkChain::resolve(&first, SymlinkChain::MAX_DEPTH).err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+    assert_eq!(PathExtImpl::build_path(&[directory, "missing"]), error.path());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+fn not_a_symlink() {
+    let path = PathExtImpl::build_path(&["test", "index.html"]);
+    let chain = SymlinkChain::resolve(&path, SymlinkChain::MAX_DEPTH).unwrap();
+    assert_eq!(vec![path.clone()], chain.hop_list);
+    assert_eq!(fs::canonicalize(&path).unwrap().to_string_lossy(), chain.target);
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn chain_through_symlinked_directory_is_not_a_loop() {
+    let directory = "symlink_chain_through_symlinked_directory";
+    let inner = PathExtImpl::build_path(&[directory, "deep", "inner"]);
+    DirectoryExtImpl::create_directory(&inner).unwrap();
+    let file = PathExtImpl::build_path(&[inner.as_str(), "file.txt"]);
+    FileExtImpl::create_file(&file).unwrap();
+
+    // alias/../x is lexically the starting link, but alias is deep/inner, so it is deep/x
+    link(directory, "alias", "deep/inner");
+    let start = link(directory, "x", "alias/../x");
+    link(&PathExtImpl::build_path(&[directory, "deep"]), "x", "inner/file.txt");
+
+    let chain = SymlinkChain::resolve(&start, SymlinkChain::MAX_DEPTH).unwrap();
+    assert_eq!(3, chain.hop_list.len());
+    assert_eq!(fs::canonicalize(&file).unwrap().to_string_lossy(), chain.target);
+
+    // the same link spelled differently via a symlinked directory is still a loop
+    link(&inner, "up", "..");
+    let around = link(&inner, "around", "up/inner/up/inner/around");
+    let error = SymlinkChain::resolve(&around, SymlinkChain::MAX_DEPTH).err().unwrap();
+    assert!(error.message().contains("loop"));
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
@@ -1,11 +1,14 @@
 use std::fs;
-use std::path::Path;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use crate::directory_entry::{EntryKind, ListDirectoryOptions};
 use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::{FileExtError, Operation};
 use crate::file_ext_impl::FileExtImpl;
-use crate::FileExt;
+use crate::filter_string::FilterString;
 use crate::path_ext_impl::PathExtImpl;
-#[cfg(target_family = "unix")]
-use crate::symbol::SYMBOL;
+use crate::symlink_chain::SymlinkChain;
+use crate::symlink_options::{SymlinkKind, SymlinkOptions};
 
 #[cfg(test)]
 mod tests;
@@ -13,152 +16,334 @@ mod tests;
 pub struct SymlinkExtImpl;
 
 impl SymlinkExtImpl {
-    pub fn does_symlink_exist(path: &str) -> bool {
-        Path::new(path).is_symlink()
+    pub fn does_symlink_exist<P: AsRef<Path>>(path: P) -> bool {
+        path.as_ref().is_symlink()
     }
 
-    #[cfg(target_family = "unix")]
-    pub fn create_symlink(symlink_path: &str, symlink_name: &str, symlink_points_to: &str) -> Result<(), String> {
+    #[cfg(any(target_family = "unix", target_family = "windows"))]
+    pub fn create_symlink<P: AsRef<Path>, Q: AsRef<Path>, R: AsRef<Path>>(symlink_path: P, symlink_name: Q, symlink_points_to: R) -> Result<(), FileExtError> {
+        SymlinkExtImpl::create_symlink_with_options(symlink_path, symlink_name, symlink_points_to, &SymlinkOptions::default())
+    }
+
+    #[cfg(any(target_family = "unix", target_family = "windows"))]
+    pub fn create_symlink_with_options<P: AsRef<Path>, Q: AsRef<Path>, R: AsRef<Path>>(symlink_path: P, symlink_name: Q, symlink_points_to: R, options: &SymlinkOptions) -> Result<(), FileExtError> {
+        let symlink_directory = symlink_path.as_ref();
+        let symlink_points_to = symlink_points_to.as_ref();
+        let path_to_symlink_included = symlink_directory.join(symlink_name);
+        let symlink = path_to_symlink_included.to_string_lossy();
+        for path in [path_to_symlink_included.as_path(), symlink_points_to] {
+            let boxed_check = FilterString::is_valid_input_path(path);
+            if boxed_check.is_err() {
+                let message = boxed_check.err().unwrap();
+                return Err(FileExtError::invalid_path(Operation::CreateSymlink, &path.to_string_lossy(), message))
+            }
+        }
 
         //check if there is already a file where symlink is going to be created
-        let path_to_symlink_included = [symlink_path, symlink_name].join(PathExtImpl::get_path_separator().as_str());
         let does_file_exist = FileExtImpl::does_file_exist(&path_to_symlink_included);
         if does_file_exist {
-            let message = format!("There is a file on a given path: {}", &path_to_symlink_included);
-            return Err(message)
+            let message = format!("There is a file on a given path: {}", symlink);
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &symlink, ErrorKind::AlreadyExists, message))
         }
         let does_directory_exist = DirectoryExtImpl::does_directory_exist(&path_to_symlink_included);
         if does_directory_exist {
-            let message = format!("There is a directory on a given path: {}", &path_to_symlink_included);
-            return Err(message)
+            let message = format!("There is a directory on a given path: {}", symlink);
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &symlink, ErrorKind::AlreadyExists, message))
         }
 
-        let boxed_resolved_path = SymlinkExtImpl::resolve_symlink_path(symlink_path,symlink_points_to);
+        // relative target is resolved against the directory of the symlink, the same way the OS does it
+        let boxed_resolved_path = SymlinkExtImpl::resolve_symlink_target(symlink_directory, symlink_points_to);
         if boxed_resolved_path.is_err() {
-            let message = boxed_resolved_path.err().unwrap();
-            return Err(message)
+            let error = boxed_resolved_path.err().unwrap();
+            return Err(error)
         }
 
-        let mut resolved_path_symlink_point_to = boxed_resolved_path.unwrap();
-
-        if !symlink_points_to.starts_with(SYMBOL.slash) {
-            let working_directory = FileExt::working_directory().unwrap();
-            resolved_path_symlink_point_to = [working_directory, resolved_path_symlink_point_to.to_string()].join(PathExtImpl::get_path_separator().as_str());
+        let mut resolved_path = boxed_resolved_path.unwrap();
+        if !resolved_path.is_absolute() {
+            let boxed_working_directory = PathExtImpl::working_directory_path();
+            if boxed_working_directory.is_err() {
+                let error = boxed_working_directory.err().unwrap();
+                return Err(error)
+            }
+            resolved_path = boxed_working_directory.unwrap().join(resolved_path);
         }
-
+        let resolved = resolved_path.to_string_lossy();
 
         //check if there is a file or directory for symlink to be created
-        let does_file_exist = FileExtImpl::does_file_exist(resolved_path_symlink_point_to.as_str());
-        let does_directory_exist = DirectoryExtImpl::does_directory_exist(resolved_path_symlink_point_to.as_str());
+        let does_file_exist = FileExtImpl::does_file_exist(&resolved_path);
+        let does_directory_exist = DirectoryExtImpl::does_directory_exist(&resolved_path);
+
+        if !does_file_exist && !does_directory_exist && !options.allow_dangling {
+            let message = format!("There is no file or directory for symlink to be created: {}", resolved);
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &resolved, ErrorKind::NotFound, message))
+        }
+
+        if options.kind == SymlinkKind::File && does_directory_exist {
+            let message = format!("Symlink kind is file, but target is a directory: {}", resolved);
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &resolved, ErrorKind::InvalidInput, message))
+        }
 
-        if !does_file_exist && !does_directory_exist   {
-            let message = format!("There is no file or directory for symlink to be created: {}", resolved_path_symlink_point_to.as_str());
-            return Err(message)
+        if options.kind == SymlinkKind::Directory && does_file_exist {
+            let message = format!("Symlink kind is directory, but target is a file: {}", resolved);
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &resolved, ErrorKind::InvalidInput, message))
         }
 
-        let boxed_symlink = std::os::unix::fs::symlink(resolved_path_symlink_point_to.as_str(), path_to_symlink_included);
+        let target = if options.keep_relative_target { symlink_points_to } else { resolved_path.as_path() };
+        let is_directory = does_directory_exist || options.kind == SymlinkKind::Directory;
+        let boxed_symlink = SymlinkExtImpl::create_link(target, &path_to_symlink_included, is_directory);
         if boxed_symlink.is_err()   {
-            let message = boxed_symlink.err().unwrap().to_string();
-            return Err(message)
+            let error = boxed_symlink.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CreateSymlink, &symlink, error))
         }
 
         Ok(())
-
     }
 
-    #[cfg(target_family = "windows")]
-    pub fn create_symlink(symlink_path: &str, symlink_name: &str, symlink_points_to: &str) -> Result<(), String> {
-        //check if there is already a file where symlink is going to be created
-        let path_to_symlink_included = [symlink_path, symlink_name].join(PathExtImpl::get_path_separator().as_str());
-        let does_file_exist = FileExtImpl::does_file_exist(&path_to_symlink_included);
-        if does_file_exist {
-            let message = format!("There is a file on a given path: {}", &path_to_symlink_included);
-            return Err(message)
-        }
-        let does_directory_exist = DirectoryExtImpl::does_directory_exist(&path_to_symlink_included);
-        if does_directory_exist {
-            let message = format!("There is a directory on a given path: {}", &path_to_symlink_included);
-            return Err(message)
+    /// Same as `resolve_symlink_path` for `Path`: absolute target is returned as is, relative target is joined
+    /// with the symlink directory and `..` components are removed along with the preceding directory
+    fn resolve_symlink_target(symlink_directory: &Path, symlink_points_to: &Path) -> Result<PathBuf, FileExtError> {
+        if symlink_points_to.has_root() || symlink_points_to.is_absolute() {
+            return Ok(symlink_points_to.to_path_buf())
         }
 
-        let boxed_resolved_path = SymlinkExtImpl::resolve_symlink_path(symlink_path,symlink_points_to);
-        if boxed_resolved_path.is_err() {
-            let message = boxed_resolved_path.err().unwrap();
-            return Err(message)
+        let mut resolved_path = symlink_directory.to_path_buf();
+        for component in symlink_points_to.components() {
+            if component != Component::ParentDir {
+                resolved_path.push(component);
+                continue;
+            }
+
+            if resolved_path.as_os_str().is_empty() {
+                let message = "not valid path for the symlink";
+                return Err(FileExtError::from_kind(Operation::ResolveSymlink, &symlink_points_to.to_string_lossy(), ErrorKind::InvalidInput, message.to_string()))
+            }
+            resolved_path.pop();
         }
 
-        let mut resolved_path = boxed_resolved_path.unwrap();
+        Ok(resolved_path)
+    }
 
-        if resolved_path.chars().count() >= 2 {
-            let second_char = resolved_path.chars().take(2).last().unwrap();
-            if second_char != ':' {
-                let working_directory = FileExt::working_directory().unwrap();
-                resolved_path = PathExtImpl::build_path(&[&working_directory, &resolved_path]);
+    /// Points existing symlink to the new target without a moment when the link is missing: temp link is created
+    /// next to it and renamed over it. Target is stored as given, relative target is resolved against the directory
+    /// of the link and has to exist. Returns the previous target.
+    /// Unix only, rename on Windows can not replace a directory symlink
+    #[cfg(target_family = "unix")]
+    pub fn replace_symlink(link: &str, new_target: &str) -> Result<String, FileExtError> {
+        let boxed_previous_target = SymlinkExtImpl::replace_symlink_path(link, new_target);
+        if boxed_previous_target.is_err() {
+            let error = boxed_previous_target.err().unwrap();
+            return Err(error)
+        }
+        Ok(boxed_previous_target.unwrap().to_string_lossy().to_string())
+    }
+
+    /// Same as `replace_symlink`, previous target is returned without lossy conversion
+    #[cfg(target_family = "unix")]
+    pub fn replace_symlink_path<P: AsRef<Path>, Q: AsRef<Path>>(link: P, new_target: Q) -> Result<PathBuf, FileExtError> {
+        let link_path = link.as_ref();
+        let new_target = new_target.as_ref();
+        let link = link_path.to_string_lossy();
+        for path in [link_path, new_target] {
+            let boxed_check = FilterString::is_valid_input_path(path);
+            if boxed_check.is_err() {
+                let message = boxed_check.err().unwrap();
+                return Err(FileExtError::invalid_path(Operation::CreateSymlink, &path.to_string_lossy(), message))
             }
         }
 
-        //check if there is a file or directory for symlink to be created
-        let does_file_exist = FileExtImpl::does_file_exist(&resolved_path);
-        let does_directory_exist = DirectoryExtImpl::does_directory_exist(&resolved_path);
+        let boxed_is_symlink = SymlinkExtImpl::is_symlink(link_path);
+        if boxed_is_symlink.is_err() {
+            let error = boxed_is_symlink.err().unwrap();
+            return Err(error)
+        }
 
-        if !does_file_exist && !does_directory_exist   {
-            let message = format!("There is no file or directory for symlink to be created: {}", &resolved_path);
-            return Err(message)
+        if !boxed_is_symlink.unwrap() {
+            let message = format!("There is no symlink at the given path: {}", link);
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &link, ErrorKind::InvalidInput, message))
         }
 
-        if does_file_exist {
-            let boxed_symlink = std::os::windows::fs::symlink_file(&resolved_path, path_to_symlink_included);
-            if boxed_symlink.is_err()   {
-                let message = boxed_symlink.err().unwrap().to_string();
-                return Err(message)
-            }
+        let boxed_previous_target = SymlinkExtImpl::symlink_points_to_path(link_path);
+        if boxed_previous_target.is_err() {
+            let error = boxed_previous_target.err().unwrap();
+            return Err(error)
+        }
+        let previous_target = boxed_previous_target.unwrap();
 
-            return  Ok(());
+        let directory = link_path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let resolved_target = directory.join(new_target);
+        if !resolved_target.exists() {
+            let message = format!("There is no file or directory for symlink to be created: {}", resolved_target.to_string_lossy());
+            return Err(FileExtError::from_kind(Operation::CreateSymlink, &resolved_target.to_string_lossy(), ErrorKind::NotFound, message))
         }
 
-        if does_directory_exist {
-            let boxed_symlink = std::os::windows::fs::symlink_dir(&resolved_path, path_to_symlink_included);
-            if boxed_symlink.is_err()   {
-                let message = boxed_symlink.err().unwrap().to_string();
-                return Err(message)
-            }
+        let boxed_swap = SymlinkExtImpl::swap_symlink(link_path, new_target);
+        if boxed_swap.is_err() {
+            let error = boxed_swap.err().unwrap();
+            return Err(error)
+        }
 
-            return Ok(());
+        // rename is atomic, so there is nothing to roll back: once it succeeded the link points to the new target.
+        // Sync only makes the switch durable, its failure is not reported so callers do not retry a completed switch
+        let synced_directory = if directory.as_os_str().is_empty() { Path::new(".") } else { directory.as_path() };
+        FileExtImpl::sync_directory(synced_directory).unwrap_or_default();
+
+        Ok(previous_target)
+    }
+
+    /// Creates temp link to the target next to the link and renames it over the link, temp link is removed on failure
+    #[cfg(target_family = "unix")]
+    fn swap_symlink(link: &Path, target: &Path) -> Result<(), FileExtError> {
+        use crate::date_time_ext::DateTimeExt;
+
+        let link_path = link.to_string_lossy();
+        let file_name = link.file_name().unwrap_or_default().to_string_lossy();
+        let temp_name = format!(".{}.{}.{}.tmp", file_name, std::process::id(), DateTimeExt::_now_unix_epoch_nanos());
+        let temp_link = link.with_file_name(temp_name);
+
+        let boxed_create = std::os::unix::fs::symlink(target, &temp_link);
+        if boxed_create.is_err() {
+            let error = boxed_create.err().unwrap();
+            return Err(FileExtError::from_io(Operation::CreateSymlink, &temp_link.to_string_lossy(), error))
+        }
+
+        let boxed_rename = fs::rename(&temp_link, link);
+        if boxed_rename.is_err() {
+            let error = boxed_rename.err().unwrap();
+            fs::remove_file(&temp_link).unwrap_or_default();
+            return Err(FileExtError::from_io(Operation::Rename, &link_path, error))
         }
 
-        let message = "Something went wrong".to_string();
-        Err(message)
+        Ok(())
+    }
+
+    #[cfg(target_family = "unix")]
+    fn create_link<P: AsRef<Path>, Q: AsRef<Path>>(target: P, link: Q, _is_directory: bool) -> std::io::Result<()> {
+        std::os::unix::fs::symlink(target, link)
+    }
 
+    #[cfg(target_family = "windows")]
+    fn create_link<P: AsRef<Path>, Q: AsRef<Path>>(target: P, link: Q, is_directory: bool) -> std::io::Result<()> {
+        if is_directory {
+            return std::os::windows::fs::symlink_dir(target, link)
+        }
+        std::os::windows::fs::symlink_file(target, link)
     }
 
-    pub fn is_symlink(path: &str) -> Result<bool, String> {
+    pub fn is_symlink<P: AsRef<Path>>(path: P) -> Result<bool, FileExtError> {
+        let path = path.as_ref();
         let boxed_symlink_metadata = fs::symlink_metadata(path);
         if boxed_symlink_metadata.is_err() {
-            let msg = boxed_symlink_metadata.err().unwrap().to_string();
-            return Err(msg)
+            let error = boxed_symlink_metadata.err().unwrap();
+            return Err(FileExtError::from_io(Operation::Metadata, &path.to_string_lossy(), error))
         }
 
         let symlink_metadata = boxed_symlink_metadata.unwrap();
         Ok(symlink_metadata.file_type().is_symlink())
     }
 
-    pub fn symlink_points_to(path: &str) -> Result<String, String> {
+    pub fn symlink_points_to(path: &str) -> Result<String, FileExtError> {
+        let boxed_points_to = SymlinkExtImpl::symlink_points_to_path(path);
+        if boxed_points_to.is_err() {
+            let error = boxed_points_to.err().unwrap();
+            return Err(error)
+        }
+
+        let points_to = boxed_points_to.unwrap();
+        let boxed_points_to = points_to.to_str();
+        if boxed_points_to.is_none() {
+            let msg = "symlink target is not valid UTF-8, use FileExtPath::symlink_points_to to read it".to_string();
+            return Err(FileExtError::from_kind(Operation::ReadSymlink, path, ErrorKind::InvalidData, msg))
+        }
+        Ok(boxed_points_to.unwrap().to_string())
+    }
+
+    pub fn symlink_points_to_path<P: AsRef<Path>>(path: P) -> Result<PathBuf, FileExtError> {
+        let path = path.as_ref();
         let boxed_path_buff = fs::read_link(path);
         if boxed_path_buff.is_err() {
-            let msg = boxed_path_buff.err().unwrap().to_string();
-            return Err(msg)
+            let error = boxed_path_buff.err().unwrap();
+            return Err(FileExtError::from_io(Operation::ReadSymlink, &path.to_string_lossy(), error))
         }
-        let path_buff = boxed_path_buff.unwrap();
-        let boxed_points_to = path_buff.as_path().to_str();
-        if boxed_points_to.is_none() {
-            let msg = "unable to read link as path".to_string();
-            return Err(msg)
+        Ok(boxed_path_buff.unwrap())
+    }
+
+    /// Checks if the path is a symlink whose target does not exist. Relative target is resolved against
+    /// the directory of the symlink. Target which is a broken symlink itself makes the path dangling too
+    pub fn is_dangling<P: AsRef<Path>>(path: P) -> Result<bool, FileExtError> {
+        let path = path.as_ref();
+        let boxed_is_symlink = SymlinkExtImpl::is_symlink(path);
+        if boxed_is_symlink.is_err() {
+            let error = boxed_is_symlink.err().unwrap();
+            return Err(error)
         }
-        let points_to = boxed_points_to.unwrap();
-        Ok(points_to.to_string())
+
+        if !boxed_is_symlink.unwrap() {
+            return Ok(false)
+        }
+
+        let boxed_points_to = SymlinkExtImpl::symlink_points_to_path(path);
+        if boxed_points_to.is_err() {
+            let error = boxed_points_to.err().unwrap();
+            return Err(error)
+        }
+
+        // joining an absolute target replaces the base
+        let symlink_directory = path.parent().unwrap_or(Path::new(""));
+        let target = symlink_directory.join(boxed_points_to.unwrap());
+        Ok(!target.exists())
+    }
+
+    /// Returns paths of dangling symlinks in the directory tree, symlinks to directories are not followed
+    pub fn find_broken_symlinks(directory: &str) -> Result<Vec<String>, FileExtError> {
+        let boxed_broken_list = SymlinkExtImpl::find_broken_symlink_paths(directory);
+        if boxed_broken_list.is_err() {
+            let error = boxed_broken_list.err().unwrap();
+            return Err(error)
+        }
+
+        let broken_list = boxed_broken_list.unwrap();
+        Ok(broken_list.iter().map(|path| path.to_string_lossy().to_string()).collect())
+    }
+
+    /// Same as `find_broken_symlinks`, paths are returned without lossy conversion
+    pub fn find_broken_symlink_paths<P: AsRef<Path>>(directory: P) -> Result<Vec<PathBuf>, FileExtError> {
+        let boxed_walker = DirectoryExtImpl::walk_directory(directory, &ListDirectoryOptions::default());
+        if boxed_walker.is_err() {
+            let error = boxed_walker.err().unwrap();
+            return Err(error)
+        }
+
+        let mut broken_list = vec![];
+        for boxed_entry in boxed_walker.unwrap() {
+            if boxed_entry.is_err() {
+                let error = boxed_entry.err().unwrap();
+                return Err(error)
+            }
+
+            let entry = boxed_entry.unwrap();
+            if entry.kind != EntryKind::Symlink {
+                continue;
+            }
+
+            let boxed_is_dangling = SymlinkExtImpl::is_dangling(&entry.os_path);
+            if boxed_is_dangling.is_err() {
+                let error = boxed_is_dangling.err().unwrap();
+                return Err(error)
+            }
+
+            if boxed_is_dangling.unwrap() {
+                broken_list.push(entry.os_path);
+            }
+        }
+
+        Ok(broken_list)
+    }
+
+    pub fn resolve_symlink_chain<P: AsRef<Path>>(path: P) -> Result<SymlinkChain, FileExtError> {
+        SymlinkChain::resolve(path, SymlinkChain::MAX_DEPTH)
     }
 
-    pub fn resolve_symlink_path(symlink_directory: &str, symlink_points_to: &str) -> Result<String, String> {
+    pub fn resolve_symlink_path(symlink_directory: &str, symlink_points_to: &str) -> Result<String, FileExtError> {
 
         // windows specific check on a link
         if symlink_points_to.chars().count() >= 2 {
@@ -179,17 +364,16 @@ impl SymlinkExtImpl {
         }
 
         let (part, symlink_after_split) = boxed_split.unwrap();
-        return if part == ".." {
+        if part == ".." {
 
             if symlink_directory.chars().count() == 0 {
                 let message = "not valid path for the symlink";
-                return Err(message.to_string())
+                return Err(FileExtError::from_kind(Operation::ResolveSymlink, symlink_points_to, ErrorKind::InvalidInput, message.to_string()))
             }
 
             let reversed_base_dir = symlink_directory.chars().rev().collect::<String>();
             let boxed_one_level_up_split = reversed_base_dir.split_once(PathExtImpl::get_path_separator().as_str());
-            if boxed_one_level_up_split.is_some() {
-                let (_cut_folder, remaining_base_dir) = boxed_one_level_up_split.unwrap();
+            if let Some((_cut_folder, remaining_base_dir)) = boxed_one_level_up_split {
                 let _symlink_directory = remaining_base_dir.chars().rev().collect::<String>();
                 SymlinkExtImpl::resolve_symlink_path(_symlink_directory.as_str(), symlink_after_split)
             } else {
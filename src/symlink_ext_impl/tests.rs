@@ -3,6 +3,8 @@ use crate::file_ext_impl::FileExtImpl;
 use crate::FileExt;
 use crate::path_ext_impl::PathExtImpl;
 use crate::symlink_ext_impl::SymlinkExtImpl;
+use crate::symlink_options::{SymlinkKind, SymlinkOptions};
+use crate::file_ext_error::FileExtError;
 
 #[test]
 fn symlink_check() {
@@ -323,7 +325,7 @@ fn resolve_symlink_path_not_valid() {
 
     let expected_error = "not valid path for the symlink";
     let actual_error = boxed_resolve.err().unwrap();
-    assert_eq!(expected_error, actual_error);
+    assert_eq!(expected_error, actual_error.message());
 }
 
 #[test]
@@ -585,3 +587,219 @@ fn symlink_points_to_symlink_which_points_to_file() {
     FileExt::delete_file(&symlink_path).unwrap();
     FileExt::delete_file(&second_symlink_path).unwrap();
 }
+
+#[test]
+#[cfg(target_family = "unix")]
+fn relative_symlink_survives_move() {
+    let release = "relative_symlink_survives_move";
+    let moved = "relative_symlink_survives_move_moved";
+    let version = PathExtImpl::build_path(&[release, "v1"]);
+    DirectoryExtImpl::create_directory(&version).unwrap();
+    let app = PathExtImpl::build_path(&[version.as_str(), "app.txt"]);
+    FileExtImpl::create_file(&app).unwrap();
+    FileExtImpl::write_file(&app, b"v1").unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, kind: SymlinkKind::Directory, ..Default::default() };
+    SymlinkExtImpl::create_symlink_with_options(release, "current", "v1", &options).unwrap();
+    let link = PathExtImpl::build_path(&[release, "current"]);
+    assert_eq!("v1", SymlinkExtImpl::symlink_points_to(&link).unwrap());
+
+    std::fs::rename(release, moved).unwrap();
+    let moved_app = PathExtImpl::build_path(&[moved, "current", "app.txt"]);
+    assert_eq!(b"v1".to_vec(), FileExtImpl::read_file(&moved_app).unwrap());
+
+    DirectoryExtImpl::delete_directory(moved).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn absolute_target_by_default() {
+    let directory = "absolute_target_by_default";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+    FileExtImpl::create_file(PathExtImpl::build_path(&[directory, "target.txt"])).unwrap();
+
+    SymlinkExtImpl::create_symlink(directory, "link", "target.txt").unwrap();
+    let points_to = SymlinkExtImpl::symlink_points_to(&PathExtImpl::build_path(&[directory, "link"])).unwrap();
+    let expected = PathExtImpl::build_path(&[FileExt::working_directory().unwrap().as_str(), directory, "target.txt"]);
+    assert_eq!(expected, points_to);
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(any(target_family = "unix", target_family = "windows"))]
+fn relative_target_is_validated_against_link_directory() {
+    let directory = "relative_target_is_validated_against_link_directory";
+    let nested = PathExtImpl::build_path(&[directory, "nested"]);
+    DirectoryExtImpl::create_directory(&nested).unwrap();
+    FileExtImpl::create_file(PathExtImpl::build_path(&[directory, "target.txt"])).unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    // target exists next to the nested directory, not inside of it
+    let error = SymlinkExtImpl::create_symlink_with_options(&nested, "link", "target.txt", &options).err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+
+    let relative_target = PathExtImpl::build_path(&["..", "target.txt"]);
+    SymlinkExtImpl::create_symlink_with_options(&nested, "link", &relative_target, &options).unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, kind: SymlinkKind::Directory, ..Default::default() };
+    let error = SymlinkExtImpl::create_symlink_with_options(&nested, "directory_link", &relative_target, &options).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    let options = SymlinkOptions { keep_relative_target: true, kind: SymlinkKind::File, ..Default::default() };
+    let error = SymlinkExtImpl::create_symlink_with_options(directory, "file_link", "nested", &options).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn dangling_symlink() {
+    let directory = "dangling_symlink";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    let error = SymlinkExtImpl::create_symlink_with_options(directory, "current", "release", &options).err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+
+    let options = SymlinkOptions { keep_relative_target: true, allow_dangling: true, kind: SymlinkKind::Directory };
+    SymlinkExtImpl::create_symlink_with_options(directory, "current", "release", &options).unwrap();
+    let link = PathExtImpl::build_path(&[directory, "current"]);
+    assert_eq!("release", SymlinkExtImpl::symlink_points_to(&link).unwrap());
+    assert!(SymlinkExtImpl::is_dangling(&link).unwrap());
+
+    // release is populated after the link is created
+    DirectoryExtImpl::create_directory(PathExtImpl::build_path(&[directory, "release"])).unwrap();
+    assert!(!SymlinkExtImpl::is_dangling(&link).unwrap());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn find_broken_symlinks() {
+    let directory = "find_broken_symlinks";
+    let nested = PathExtImpl::build_path(&[directory, "nested"]);
+    DirectoryExtImpl::create_directory(&nested).unwrap();
+    let file = PathExtImpl::build_path(&[directory, "file.txt"]);
+    FileExtImpl::create_file(&file).unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, allow_dangling: true, ..Default::default() };
+    SymlinkExtImpl::create_symlink_with_options(directory, "valid", "file.txt", &options).unwrap();
+    SymlinkExtImpl::create_symlink_with_options(&nested, "broken", "missing.txt", &options).unwrap();
+    // link to a broken link is broken as well
+    SymlinkExtImpl::create_symlink_with_options(directory, "chained", "nested/broken", &options).unwrap();
+
+    assert!(!SymlinkExtImpl::is_dangling(&file).unwrap());
+    assert!(SymlinkExtImpl::is_dangling("find_broken_symlinks/missing.txt").is_err());
+
+    let mut broken_list = SymlinkExtImpl::find_broken_symlinks(directory).unwrap();
+    broken_list.sort();
+    let expected = vec![
+        PathExtImpl::build_path(&[directory, "chained"]),
+        PathExtImpl::build_path(&[nested.as_str(), "broken"]),
+    ];
+    assert_eq!(expected, broken_list);
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn replace_symlink() {
+    let directory = "replace_symlink";
+    let first_release = PathExtImpl::build_path(&[directory, "releases", "v41"]);
+    let second_release = PathExtImpl::build_path(&[directory, "releases", "v42"]);
+    DirectoryExtImpl::create_directory(&first_release).unwrap();
+    DirectoryExtImpl::create_directory(&second_release).unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    SymlinkExtImpl::create_symlink_with_options(directory, "current", "releases/v41", &options).unwrap();
+    let link = PathExtImpl::build_path(&[directory, "current"]);
+
+    let previous_target = SymlinkExtImpl::replace_symlink(&link, "releases/v42").unwrap();
+    assert_eq!("releases/v41", previous_target);
+    assert_eq!("releases/v42", SymlinkExtImpl::symlink_points_to(&link).unwrap());
+
+    // temp link does not stay around
+    let name_list: Vec<String> = std::fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(2, name_list.len());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn replaced_symlink_is_never_missing() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let directory = "replaced_symlink_is_never_missing";
+    DirectoryExtImpl::create_directory(PathExtImpl::build_path(&[directory, "blue"])).unwrap();
+    DirectoryExtImpl::create_directory(PathExtImpl::build_path(&[directory, "green"])).unwrap();
+
+    let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    SymlinkExtImpl::create_symlink_with_options(directory, "current", "blue", &options).unwrap();
+    let link = PathExtImpl::build_path(&[directory, "current"]);
+
+    let is_done = Arc::new(AtomicBool::new(false));
+    let reader_is_done = is_done.clone();
+    let reader_link = link.clone();
+    let reader = std::thread::spawn(move || {
+        let mut missing_count = 0;
+        while !reader_is_done.load(Ordering::Relaxed) {
+            if std::fs::read_link(&reader_link).is_err() {
+                missing_count += 1;
+            }
+        }
+        missing_count
+    });
+
+    for index in 0..200 {
+        let target = if index % 2 == 0 { "green" } else { "blue" };
+        SymlinkExtImpl::replace_symlink(&link, target).unwrap();
+    }
+    is_done.store(true, Ordering::Relaxed);
+    assert_eq!(0, reader.join().unwrap());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+#[cfg(target_family = "unix")]
+fn replace_symlink_validates_input() {
+    let directory = "replace_symlink_validates_input";
+    DirectoryExtImpl::create_directory(directory).unwrap();
+    let file = PathExtImpl::build_path(&[directory, "file.txt"]);
+    FileExtImpl::create_file(&file).unwrap();
+
+    let error = SymlinkExtImpl::replace_symlink(&file, "file.txt").err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    let options = SymlinkOptions { keep_relative_target: true, ..Default::default() };
+    SymlinkExtImpl::create_symlink_with_options(directory, "current", "file.txt", &options).unwrap();
+    let link = PathExtImpl::build_path(&[directory, "current"]);
+
+    // missing target leaves the link as it was
+    let error = SymlinkExtImpl::replace_symlink(&link, "missing.txt").err().unwrap();
+    assert!(matches!(error, FileExtError::NotFound { .. }));
+    assert_eq!("file.txt", SymlinkExtImpl::symlink_points_to(&link).unwrap());
+
+    DirectoryExtImpl::delete_directory(directory).unwrap();
+}
+
+#[test]
+fn create_symlink_rejected_path() {
+    let options = SymlinkOptions { keep_relative_target: true, allow_dangling: true, ..Default::default() };
+
+    let error = SymlinkExtImpl::create_symlink_with_options("test", "link;rm", "index.html", &options).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+    assert!(!SymlinkExtImpl::does_symlink_exist(PathExtImpl::build_path(&["test", "link;rm"])));
+
+    let error = SymlinkExtImpl::create_symlink_with_options("test", "link", "index.html|rm", &options).err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+    assert_eq!("index.html|rm", error.path());
+}
@@ -0,0 +1,25 @@
+/// Type of the entry symlink points to. Windows needs it to pick file or directory symlink,
+/// on Unix it is only checked against the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymlinkKind {
+    /// Detected from the target
+    #[default]
+    Auto,
+    /// Target has to be a file
+    File,
+    /// Target has to be a directory
+    Directory,
+}
+
+/// Options for `create_symlink_with_options`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SymlinkOptions {
+    /// Store the target exactly as given, so the link keeps working when the directory tree is moved.
+    /// Relative target is resolved against the directory of the link, both for validation and by the OS.
+    /// Otherwise the target is stored as an absolute path
+    pub keep_relative_target: bool,
+    pub kind: SymlinkKind,
+    /// Create the link even if the target does not exist yet, `kind` is checked only for existing target.
+    /// On Windows dangling link with `SymlinkKind::Auto` is created as a file symlink
+    pub allow_dangling: bool,
+}
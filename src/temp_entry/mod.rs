@@ -0,0 +1,112 @@
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::date_time_ext::DateTimeExt;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::filter_string::FilterString;
+use crate::path_style::PathStyle;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of names tried before giving up, collision of random names means something is wrong
+pub(crate) const MAX_ATTEMPT_COUNT: usize = 100;
+
+static NAME_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// File created by `FileExt::create_temp_file`, it is deleted when the guard is dropped
+#[derive(Debug)]
+pub struct TempFile {
+    path: String,
+    keep: bool,
+}
+
+impl TempFile {
+    pub(crate) fn new(path: String) -> TempFile {
+        TempFile { path, keep: false }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Disarms the guard, file stays on disk. Returns its path
+    pub fn keep(mut self) -> String {
+        self.keep = true;
+        self.path.clone()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Directory created by `FileExt::create_temp_directory`, it is deleted with all of its content
+/// when the guard is dropped. Symlinks inside are removed, never followed
+#[derive(Debug)]
+pub struct TempDirectory {
+    path: String,
+    keep: bool,
+}
+
+impl TempDirectory {
+    pub(crate) fn new(path: String) -> TempDirectory {
+        TempDirectory { path, keep: false }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Disarms the guard, directory stays on disk. Returns its path
+    pub fn keep(mut self) -> String {
+        self.keep = true;
+        self.path.clone()
+    }
+}
+
+impl Drop for TempDirectory {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+/// Prefix and suffix become part of a single file name, so separators are not allowed in them
+pub(crate) fn check_affix(operation: Operation, affix: &str) -> Result<(), FileExtError> {
+    if affix.is_empty() {
+        return Ok(())
+    }
+
+    if affix.contains(['/', PathStyle::native().separator()]) {
+        let message = format!("prefix and suffix must not contain path separator: {}", affix);
+        return Err(FileExtError::from_kind(operation, affix, ErrorKind::InvalidInput, message))
+    }
+
+    let boxed_check = FilterString::is_valid_input_string(affix);
+    if boxed_check.is_err() {
+        let message = boxed_check.err().unwrap();
+        return Err(FileExtError::invalid_path(operation, affix, message))
+    }
+
+    Ok(())
+}
+
+/// Returns path in the temp folder with a name that is hard to guess: random state of the hasher
+/// is seeded by the operating system, counter keeps names distinct within the process
+pub(crate) fn random_path(temp_folder: &str, prefix: &str, suffix: &str) -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    hasher.write_u128(DateTimeExt::_now_unix_epoch_nanos());
+    hasher.write_u64(NAME_COUNTER.fetch_add(1, Ordering::Relaxed));
+
+    let name = format!("{}{:016x}{}", prefix, hasher.finish(), suffix);
+    PathStyle::native().join(&[temp_folder, name.as_str()])
+}
@@ -0,0 +1,80 @@
+use crate::directory_ext_impl::DirectoryExtImpl;
+use crate::file_ext_error::FileExtError;
+use crate::file_ext_impl::FileExtImpl;
+use crate::path_ext_impl::PathExtImpl;
+use crate::path_style::PathStyle;
+
+#[test]
+fn temp_file_is_deleted_on_drop() {
+    let temp_file = FileExtImpl::create_temp_file("temp_file_is_deleted_on_drop-", ".txt").unwrap();
+    let path = temp_file.path().to_string();
+
+    let temp_folder = PathExtImpl::get_temp_folder_path().unwrap();
+    assert_eq!(Some(temp_folder), PathStyle::native().parent(&path));
+    let file_name = PathStyle::native().file_name(&path).unwrap();
+    assert!(file_name.starts_with("temp_file_is_deleted_on_drop-"));
+    assert!(file_name.ends_with(".txt"));
+
+    FileExtImpl::write_file(&path, b"content").unwrap();
+    assert_eq!(b"content".to_vec(), FileExtImpl::read_file(&path).unwrap());
+
+    drop(temp_file);
+    assert!(!FileExtImpl::does_file_exist(&path));
+}
+
+#[test]
+fn temp_file_names_are_unique() {
+    let first = FileExtImpl::create_temp_file("temp_file_names_are_unique-", "").unwrap();
+    let second = FileExtImpl::create_temp_file("temp_file_names_are_unique-", "").unwrap();
+    assert_ne!(first.path(), second.path());
+}
+
+#[test]
+fn kept_temp_file_stays() {
+    let temp_file = FileExtImpl::create_temp_file("kept_temp_file_stays-", "").unwrap();
+    let path = temp_file.keep();
+    assert!(FileExtImpl::does_file_exist(&path));
+
+    FileExtImpl::delete_file(&path).unwrap();
+}
+
+#[test]
+fn temp_directory_is_deleted_with_content_on_drop() {
+    let temp_directory = DirectoryExtImpl::create_temp_directory("temp_directory_is_deleted_with_content_on_drop-").unwrap();
+    let path = temp_directory.path().to_string();
+    assert!(DirectoryExtImpl::does_directory_exist(&path));
+
+    let nested = PathExtImpl::build_path(&[path.as_str(), "nested"]);
+    DirectoryExtImpl::create_directory(&nested).unwrap();
+    FileExtImpl::create_file(PathExtImpl::build_path(&[nested.as_str(), "file.txt"])).unwrap();
+
+    drop(temp_directory);
+    assert!(!DirectoryExtImpl::does_directory_exist(&path));
+}
+
+#[test]
+fn separator_in_prefix_is_rejected() {
+    let error = FileExtImpl::create_temp_file("../escape-", "").err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    let error = DirectoryExtImpl::create_temp_directory("nested/").err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidInput { .. }));
+
+    let error = FileExtImpl::create_temp_file("", ".t\0xt").err().unwrap();
+    assert!(matches!(error, FileExtError::InvalidPath { .. }));
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn temp_entries_are_private() {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_file = FileExtImpl::create_temp_file("temp_entries_are_private-", "").unwrap();
+    let mode = fs::metadata(temp_file.path()).unwrap().permissions().mode();
+    assert_eq!(0o600, mode & 0o777);
+
+    let temp_directory = DirectoryExtImpl::create_temp_directory("temp_entries_are_private-").unwrap();
+    let mode = fs::metadata(temp_directory.path()).unwrap().permissions().mode();
+    assert_eq!(0o700, mode & 0o777);
+}
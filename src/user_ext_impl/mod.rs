@@ -1,4 +1,8 @@
-use std::process::Command;
+use std::env;
+use std::io::ErrorKind;
+use crate::file_ext_error::{FileExtError, Operation};
+use crate::symbol::SYMBOL;
+use crate::user_info::UserInfo;
 
 #[cfg(test)]
 mod tests;
@@ -6,85 +10,107 @@ mod tests;
 pub struct UserExtImpl {}
 
 impl UserExtImpl {
-    #[cfg(target_family = "unix")]
-    pub fn get_current_user() -> Result<String, String> {
-        let boxed_whoami = Command::new("whoami")
-            .output();
-
-        if boxed_whoami.is_err() {
-            let message = boxed_whoami.err().unwrap().to_string();
-            return Err(message);
+    pub fn get_current_user() -> Result<String, FileExtError> {
+        let boxed_user_info = UserExtImpl::get_current_user_info();
+        if boxed_user_info.is_err() {
+            let error = boxed_user_info.err().unwrap();
+            return Err(error)
         }
 
-        let boxed_current_user = String::from_utf8(boxed_whoami.unwrap().stdout);
-        if boxed_current_user.is_err() {
-            let message = boxed_current_user.err().unwrap().to_string();
-            return Err(message);
-        }
-
-        let current_user = boxed_current_user.unwrap();
-        let user = str::replace(current_user.as_str(), "\n", "");
-
-        Ok(user)
+        Ok(boxed_user_info.unwrap().name)
     }
 
-    #[cfg(target_family = "windows")]
-    pub fn get_current_user() -> Result<String, String> {
-        let boxed_whoami = Command::new("whoami")
-            .output();
-
-        if boxed_whoami.is_err() {
-            let message = boxed_whoami.err().unwrap().to_string();
-            return Err(message);
+    /// Looks up `/etc/passwd` entry of the effective user, which is known on Linux from `/proc/self/status`.
+    /// Elsewhere, or if the uid has no entry (containers running with arbitrary uid), user name is taken
+    /// from `USER` or `LOGNAME` and the rest is filled from the entry with that name or from the environment
+    #[cfg(not(target_family = "windows"))]
+    pub fn get_current_user_info() -> Result<UserInfo, FileExtError> {
+        let passwd = std::fs::read_to_string("/etc/passwd").unwrap_or_default();
+        let boxed_status = std::fs::read_to_string("/proc/self/status");
+        let (uid, gid) = boxed_status
+            .map(|status| UserExtImpl::effective_ids(&status))
+            .unwrap_or_default();
+
+        if uid.is_some() {
+            let boxed_user_info = UserInfo::find_in_passwd(&passwd, |user_info| user_info.uid == uid);
+            if let Some(user_info) = boxed_user_info {
+                return Ok(user_info)
+            }
         }
 
-        let boxed_current_user = String::from_utf8(boxed_whoami.unwrap().stdout);
-        if boxed_current_user.is_err() {
-            let message = boxed_current_user.err().unwrap().to_string();
-            return Err(message);
+        let boxed_name = UserExtImpl::env_value(&["USER", "LOGNAME"]);
+        if boxed_name.is_none() {
+            let message = "user is not found in /etc/passwd and neither USER nor LOGNAME is set".to_string();
+            return Err(FileExtError::from_kind(Operation::CurrentUser, SYMBOL.empty_string, ErrorKind::NotFound, message))
         }
-
-        let current_user = boxed_current_user.unwrap();
-
-        let boxed_domain_user = current_user.split_once("\\");
-        if boxed_domain_user.is_none() {
-            let message = format!("unable to extract user: {}", current_user);
-            return Err(message);
+        let name = boxed_name.unwrap();
+
+        // when uid is known but has no entry, entry found by the name from environment would belong to someone else
+        let boxed_user_info = UserInfo::find_in_passwd(&passwd, |user_info| user_info.name == name);
+        if uid.is_none() {
+            if let Some(user_info) = boxed_user_info {
+                return Ok(user_info)
+            }
         }
 
-        let (_domain, user) = boxed_domain_user.unwrap();
-
-        let user = str::replace(user, "\r\n", "");
-
-        Ok(user.to_string())
+        let user_info = UserInfo {
+            name,
+            uid,
+            gid,
+            home: UserExtImpl::env_value(&["HOME"]),
+            shell: UserExtImpl::env_value(&["SHELL"]),
+        };
+        Ok(user_info)
     }
 
     #[cfg(target_family = "windows")]
-    pub fn get_current_user_domain() -> Result<String, String> {
-        let boxed_whoami = Command::new("whoami")
-            .output();
-
-        if boxed_whoami.is_err() {
-            let message = boxed_whoami.err().unwrap().to_string();
-            return Err(message);
-        }
-
-        let boxed_current_user = String::from_utf8(boxed_whoami.unwrap().stdout);
-        if boxed_current_user.is_err() {
-            let message = boxed_current_user.err().unwrap().to_string();
-            return Err(message);
+    pub fn get_current_user_info() -> Result<UserInfo, FileExtError> {
+        let boxed_name = UserExtImpl::env_value(&["USERNAME"]);
+        if boxed_name.is_none() {
+            let message = "USERNAME is not set".to_string();
+            return Err(FileExtError::from_kind(Operation::CurrentUser, SYMBOL.empty_string, ErrorKind::NotFound, message))
         }
 
-        let current_user = boxed_current_user.unwrap();
+        let user_info = UserInfo {
+            name: boxed_name.unwrap(),
+            uid: None,
+            gid: None,
+            home: UserExtImpl::env_value(&["USERPROFILE"]),
+            shell: UserExtImpl::env_value(&["COMSPEC"]),
+        };
+        Ok(user_info)
+    }
 
-        let boxed_domain_user = current_user.split_once("\\");
-        if boxed_domain_user.is_none() {
-            let message = format!("unable to extract user: {}", current_user);
-            return Err(message);
+    #[cfg(target_family = "windows")]
+    pub fn get_current_user_domain() -> Result<String, FileExtError> {
+        let boxed_domain = UserExtImpl::env_value(&["USERDOMAIN"]);
+        if boxed_domain.is_none() {
+            let message = "USERDOMAIN is not set".to_string();
+            return Err(FileExtError::from_kind(Operation::CurrentUser, SYMBOL.empty_string, ErrorKind::NotFound, message))
         }
 
-        let (domain, _user) = boxed_domain_user.unwrap();
+        Ok(boxed_domain.unwrap())
+    }
+
+    /// Returns effective uid and gid from `Uid:` and `Gid:` lines of `/proc/self/status`,
+    /// each line lists real, effective, saved and filesystem ids
+    #[cfg(not(target_family = "windows"))]
+    pub(crate) fn effective_ids(status: &str) -> (Option<u32>, Option<u32>) {
+        let effective_id = |key: &str| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix(key))
+                .and_then(|id_list| id_list.split_whitespace().nth(1))
+                .and_then(|id| id.parse::<u32>().ok())
+        };
+
+        (effective_id("Uid:"), effective_id("Gid:"))
+    }
 
-        Ok(domain.to_string())
+    fn env_value(name_list: &[&str]) -> Option<String> {
+        name_list
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
     }
-}
\ No newline at end of file
+}
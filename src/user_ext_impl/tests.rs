@@ -25,4 +25,30 @@ fn current_user_domain() {
     FileExtImpl::create_file(path).unwrap();
     FileExtImpl::write_file(path, boxed_user_domain.unwrap().as_bytes()).unwrap();
     FileExtImpl::delete_file(path).unwrap();
-}
\ No newline at end of file
+}
+#[test]
+fn current_user_info() {
+    let user_info = UserExtImpl::get_current_user_info().unwrap();
+    assert!(!user_info.name.is_empty());
+    assert_eq!(user_info.name, UserExtImpl::get_current_user().unwrap());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn current_user_matches_process_owner() {
+    use std::os::unix::fs::MetadataExt;
+
+    // /proc/self is owned by the effective user of the process
+    let metadata = std::fs::metadata("/proc/self").unwrap();
+    let user_info = UserExtImpl::get_current_user_info().unwrap();
+    assert_eq!(Some(metadata.uid()), user_info.uid);
+    assert_eq!(Some(metadata.gid()), user_info.gid);
+}
+
+#[test]
+#[cfg(not(target_family = "windows"))]
+fn effective_ids() {
+    let status = "Name:\tcat\nUid:\t1000\t1001\t1002\t1003\nGid:\t100\t101\t102\t103\n";
+    assert_eq!((Some(1001), Some(101)), UserExtImpl::effective_ids(status));
+    assert_eq!((None, None), UserExtImpl::effective_ids("Name:\tcat\n"));
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests;
+
+/// Account of the user running the process.
+///
+/// On Unix it is taken from `/etc/passwd` entry of the effective user, fields that can not be
+/// found there (for example on macOS, where accounts live in the directory service) fall back to
+/// `USER`/`LOGNAME`, `HOME` and `SHELL` environment variables. On Windows `uid` and `gid` are always `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserInfo {
+    pub name: String,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub home: Option<String>,
+    pub shell: Option<String>,
+}
+
+impl UserInfo {
+    /// Parses `name:password:uid:gid:gecos:home:shell` line of `/etc/passwd`,
+    /// comments, empty lines and NIS entries (`+`, `-`) are skipped
+    pub(crate) fn from_passwd_line(line: &str) -> Option<UserInfo> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() || line.starts_with(['#', '+', '-']) {
+            return None
+        }
+
+        let field_list: Vec<&str> = line.split(':').collect();
+        if field_list.len() != 7 || field_list[0].is_empty() {
+            return None
+        }
+
+        let boxed_uid = field_list[2].parse::<u32>();
+        let boxed_gid = field_list[3].parse::<u32>();
+        if boxed_uid.is_err() || boxed_gid.is_err() {
+            return None
+        }
+
+        let user_info = UserInfo {
+            name: field_list[0].to_string(),
+            uid: Some(boxed_uid.unwrap()),
+            gid: Some(boxed_gid.unwrap()),
+            home: UserInfo::non_empty(field_list[5]),
+            shell: UserInfo::non_empty(field_list[6]),
+        };
+        Some(user_info)
+    }
+
+    /// Returns the first entry of `/etc/passwd` content matching the predicate
+    pub(crate) fn find_in_passwd<F: Fn(&UserInfo) -> bool>(passwd: &str, predicate: F) -> Option<UserInfo> {
+        passwd
+            .lines()
+            .filter_map(UserInfo::from_passwd_line)
+            .find(|user_info| predicate(user_info))
+    }
+
+    fn non_empty(field: &str) -> Option<String> {
+        if field.is_empty() {
+            return None
+        }
+        Some(field.to_string())
+    }
+}
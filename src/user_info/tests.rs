@@ -0,0 +1,57 @@
+use crate::user_info::UserInfo;
+
+const PASSWD: &str = "# local accounts
+root:x:0:0:root:/root:/bin/bash
++nis_user::::::
+daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin
+broken:x:not_a_number:1:broken:/home/broken:/bin/sh
+nohome:x:1001:1001:::
+alice:x:1000:1000:Alice,,,:/home/alice:/bin/zsh
+";
+
+#[test]
+fn passwd_line() {
+    let user_info = UserInfo::from_passwd_line("alice:x:1000:1000:Alice,,,:/home/alice:/bin/zsh\n").unwrap();
+    let expected = UserInfo {
+        name: "alice".to_string(),
+        uid: Some(1000),
+        gid: Some(1000),
+        home: Some("/home/alice".to_string()),
+        shell: Some("/bin/zsh".to_string()),
+    };
+    assert_eq!(expected, user_info);
+}
+
+#[test]
+fn skipped_passwd_line() {
+    let line_list = [
+        "",
+        "# comment",
+        "+nis_user::::::",
+        "-excluded::::::",
+        "short:x:1000:1000",
+        ":x:1000:1000::/home:/bin/sh",
+        "broken:x:not_a_number:1:broken:/home/broken:/bin/sh",
+    ];
+    for line in line_list {
+        assert_eq!(None, UserInfo::from_passwd_line(line), "{}", line);
+    }
+}
+
+#[test]
+fn empty_home_and_shell() {
+    let user_info = UserInfo::from_passwd_line("nohome:x:1001:1001:::").unwrap();
+    assert_eq!(None, user_info.home);
+    assert_eq!(None, user_info.shell);
+}
+
+#[test]
+fn find_by_uid_and_name() {
+    let user_info = UserInfo::find_in_passwd(PASSWD, |user_info| user_info.uid == Some(1000)).unwrap();
+    assert_eq!("alice", user_info.name);
+
+    let user_info = UserInfo::find_in_passwd(PASSWD, |user_info| user_info.name == "daemon").unwrap();
+    assert_eq!(Some(1), user_info.uid);
+
+    assert_eq!(None, UserInfo::find_in_passwd(PASSWD, |user_info| user_info.uid == Some(4242)));
+}
@@ -0,0 +1,36 @@
+use std::fs::OpenOptions;
+
+#[cfg(test)]
+mod tests;
+
+/// Describes how `write_file_with_mode` opens the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Appends to the end of existing file, fails if there is no file
+    Append,
+    /// Replaces content of existing file, fails if there is no file
+    Overwrite,
+    /// Creates a new file, fails if file already exists
+    CreateNew,
+    /// Appends to the end of the file, creates file if it does not exist
+    CreateOrAppend,
+    /// Replaces content of the file, creates file if it does not exist
+    CreateOrTruncate,
+}
+
+impl WriteMode {
+    pub(crate) fn open_options(&self) -> OpenOptions {
+        let mut open_options = OpenOptions::new();
+        open_options.read(false).write(true);
+
+        match self {
+            WriteMode::Append => open_options.append(true),
+            WriteMode::Overwrite => open_options.truncate(true),
+            WriteMode::CreateNew => open_options.create_new(true),
+            WriteMode::CreateOrAppend => open_options.create(true).append(true),
+            WriteMode::CreateOrTruncate => open_options.create(true).truncate(true),
+        };
+
+        open_options
+    }
+}
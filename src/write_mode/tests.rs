@@ -0,0 +1,29 @@
+use std::io::{ErrorKind, Write};
+use crate::write_mode::WriteMode;
+
+#[test]
+fn open_options() {
+    let path = "test/write_mode-open_options.txt";
+
+    let boxed_open = WriteMode::Append.open_options().open(path);
+    assert_eq!(ErrorKind::NotFound, boxed_open.err().unwrap().kind());
+
+    let boxed_open = WriteMode::Overwrite.open_options().open(path);
+    assert_eq!(ErrorKind::NotFound, boxed_open.err().unwrap().kind());
+
+    let mut file = WriteMode::CreateNew.open_options().open(path).unwrap();
+    file.write_all(b"data").unwrap();
+
+    let boxed_open = WriteMode::CreateNew.open_options().open(path);
+    assert_eq!(ErrorKind::AlreadyExists, boxed_open.err().unwrap().kind());
+
+    let mut file = WriteMode::CreateOrAppend.open_options().open(path).unwrap();
+    file.write_all(b"123").unwrap();
+    assert_eq!(b"data123".to_vec(), std::fs::read(path).unwrap());
+
+    let mut file = WriteMode::CreateOrTruncate.open_options().open(path).unwrap();
+    file.write_all(b"new").unwrap();
+    assert_eq!(b"new".to_vec(), std::fs::read(path).unwrap());
+
+    std::fs::remove_file(path).unwrap();
+}